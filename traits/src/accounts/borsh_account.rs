@@ -1,24 +1,85 @@
 use crate::accounts::mutable::ReadOnly;
-use crate::error::{AnchorError, AnchorResult};
+use crate::constraints::all::impl_supports_constraint_tuples;
+use crate::constraints::field_cmp::FieldCmp;
+use crate::constraints::has_one::HasOne;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::account_data::AccountData;
-use crate::traits::constraint::SupportsConstraint;
+use crate::traits::constraint::{AccountInfoConstraint, DataConstraint, SupportsConstraint};
 use crate::traits::maybe_bool::{MaybeBool, True};
 use crate::traits::program::{CurrentProgram, GetProgramId};
+use crate::traits::seeds::{DataSeeds, StoredBump};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use borsh::{BorshDeserialize, BorshSerialize};
 use core::marker::PhantomData;
 use core::ops::DerefMut;
+use core::panic::Location;
 use derive_more::Deref;
 use derive_where::derive_where;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
 
+/// Data deserialized on decode, which is only mutable when `A` is writable.
+///
+/// ```
+/// # use borsh::io::{Read, Result, Write};
+/// # use borsh::{BorshDeserialize, BorshSerialize};
+/// use traits::accounts::borsh_account::BorshAccount;
+/// use traits::accounts::mutable::Mut;
+/// # use traits::traits::account_data::AccountData;
+///
+/// struct Counter(u64);
+/// # impl AccountData for Counter {
+/// #     const DISCRIMINANT: &'static [u8] = &[1];
+/// # }
+/// # impl BorshSerialize for Counter {
+/// #     fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+/// #         self.0.serialize(writer)
+/// #     }
+/// # }
+/// # impl BorshDeserialize for Counter {
+/// #     fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+/// #         u64::deserialize_reader(reader).map(Self)
+/// #     }
+/// # }
+///
+/// fn increment(counter: &mut BorshAccount<Counter, Mut>) {
+///     counter.0 += 1;
+/// }
+/// ```
+///
+/// ```compile_fail,E0594
+/// # use borsh::io::{Read, Result, Write};
+/// # use borsh::{BorshDeserialize, BorshSerialize};
+/// use traits::accounts::borsh_account::BorshAccount;
+/// use traits::accounts::mutable::ReadOnly;
+/// # use traits::traits::account_data::AccountData;
+///
+/// struct Counter(u64);
+/// # impl AccountData for Counter {
+/// #     const DISCRIMINANT: &'static [u8] = &[1];
+/// # }
+/// # impl BorshSerialize for Counter {
+/// #     fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+/// #         self.0.serialize(writer)
+/// #     }
+/// # }
+/// # impl BorshDeserialize for Counter {
+/// #     fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+/// #         u64::deserialize_reader(reader).map(Self)
+/// #     }
+/// # }
+///
+/// fn increment(counter: &mut BorshAccount<Counter, ReadOnly>) {
+///     counter.0 += 1;
+/// }
+/// ```
 #[derive_where(Clone; T: Clone, A: Clone)]
 #[derive(Deref)]
-pub struct BorshAccount<T, A = ReadOnly<AccountInfo>, P = CurrentProgram>
+pub struct BorshAccount<T, A = ReadOnly<AccountInfo>, P = CurrentProgram, E = ()>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount,
@@ -28,8 +89,9 @@ where
     data: T,
     account: A,
     _program: PhantomData<fn() -> P>,
+    _error: PhantomData<fn() -> E>,
 }
-impl<T, A, P> DerefMut for BorshAccount<T, A, P>
+impl<T, A, P, E> DerefMut for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount<Mutable = True>,
@@ -39,7 +101,7 @@ where
         &mut self.data
     }
 }
-impl<T, A, P> Accounts for BorshAccount<T, A, P>
+impl<T, A, P, E> Accounts for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount,
@@ -55,7 +117,7 @@ where
         A::to_account_infos(&self.account)
     }
 }
-unsafe impl<T, A, P> SingleAccount for BorshAccount<T, A, P>
+unsafe impl<T, A, P, E> SingleAccount for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount,
@@ -69,28 +131,31 @@ where
         A::account_info_ref(&self.account)
     }
 }
-impl<T, A, P, Arg> DecodeAccounts<Arg> for BorshAccount<T, A, P>
+impl<T, A, P, E, Arg> DecodeAccounts<Arg> for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount + DecodeAccounts<Arg>,
     P: GetProgramId,
+    E: CustomErrorGenerator + Default,
 {
     fn try_accounts(
         accounts_context: &mut AccountsContext,
         accounts: &mut impl Iterator<Item = AccountInfo>,
         arg: Arg,
     ) -> AnchorResult<Self> {
+        let location = Location::caller();
         let account = A::try_accounts(accounts_context, accounts, arg)?;
 
         let parse_data = |data_bytes: &[u8]| {
-            if data_bytes.len() < T::DISCRIMINANT.len()
-                || &data_bytes[..T::DISCRIMINANT.len()] != T::DISCRIMINANT
-            {
-                Err(AnchorError::InvalidAccountData)
+            if data_bytes.len() < T::DISCRIMINANT.len() {
+                Err(E::default().generate(ErrorCode::AccountDiscriminatorNotFound.into()))
+            } else if &data_bytes[..T::DISCRIMINANT.len()] != T::DISCRIMINANT {
+                Err(E::default().generate(ErrorCode::AccountDiscriminatorMismatch.into()))
             } else {
                 T::try_from_slice(&data_bytes[T::DISCRIMINANT.len()..])
-                    .map_err(|_| AnchorError::InvalidAccountData)
+                    .map_err(|_| E::default().generate(ErrorCode::AccountDidNotDeserialize.into()))
             }
+            .map_err(|error| error.logged(location, Some(account.key())))
         };
 
         if const { A::Mutable::IS_FALSE } {
@@ -100,6 +165,7 @@ where
                 data: parse_data(data_bytes)?,
                 account,
                 _program: PhantomData,
+                _error: PhantomData,
             })
         } else {
             let data_bytes = account.account_info_ref().try_borrow_data()?;
@@ -109,46 +175,106 @@ where
                 data,
                 account,
                 _program: PhantomData,
+                _error: PhantomData,
             })
         }
     }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        A::size_hint()
+    }
 }
-impl<T, A, P, Arg> ValidateAccounts<Arg> for BorshAccount<T, A, P>
+impl<T, A, P, E, Arg> ValidateAccounts<Arg> for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount + ValidateAccounts<Arg>,
     P: GetProgramId,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: Arg) -> AnchorResult {
-        if self.account.owner() != P::program_id(accounts_context) {
-            return Err(AnchorError::InvalidAccountOwner);
+        let program_id = P::program_id(accounts_context);
+        if self.account.owner() != program_id {
+            accounts_context.report::<Self>(
+                E::default().generate(
+                    AnchorError::from(ErrorCode::AccountOwnedByWrongProgram)
+                        .with_pubkeys(*self.account.owner(), *program_id),
+                ),
+                Location::caller(),
+                Some(self.account.key()),
+            )?;
         }
         A::validate(&mut self.account, accounts_context, arg)
     }
 }
-impl<T, A, P, Arg> CleanupAccounts<Arg> for BorshAccount<T, A, P>
+impl<T, A, P, E, Arg> CleanupAccounts<Arg> for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount + CleanupAccounts<Arg>,
     P: GetProgramId,
+    E: CustomErrorGenerator + Default,
 {
     fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: Arg) -> AnchorResult {
+        let location = Location::caller();
         if const { A::Mutable::IS_TRUE } && self.account.account_info_ref().is_writable() {
             borsh::to_writer(
                 &mut *self.account_info_ref().try_borrow_mut_data()?,
                 &self.data,
             )
-            .map_err(|_| AnchorError::InvalidAccountData)?;
+            .map_err(|_| {
+                E::default()
+                    .generate(ErrorCode::AccountDidNotSerialize.into())
+                    .logged(location, Some(self.account.key()))
+            })?;
         }
 
         A::cleanup(&mut self.account, accounts_context, arg)
     }
 }
-impl<T, A, P, C> SupportsConstraint<C> for BorshAccount<T, A, P>
+impl<T, A, P, E> StoredBump for BorshAccount<T, A, P, E>
+where
+    T: AccountData + BorshSerialize + BorshDeserialize + StoredBump,
+    A: SingleAccount,
+    P: GetProgramId,
+{
+    #[inline]
+    fn stored_bump(&self) -> AnchorResult<u8> {
+        T::stored_bump(&self.data)
+    }
+}
+impl<T, A, P, E> DataSeeds for BorshAccount<T, A, P, E>
+where
+    T: AccountData + BorshSerialize + BorshDeserialize + DataSeeds,
+    A: SingleAccount,
+    P: GetProgramId,
+{
+    type Seeds = T::Seeds;
+
+    #[inline]
+    fn data_seeds(&self, context: &AccountsContext) -> AnchorResult<Self::Seeds> {
+        T::data_seeds(&self.data, context)
+    }
+}
+impl<T, A, P, E> SignerSeeds for BorshAccount<T, A, P, E>
+where
+    T: AccountData + BorshSerialize + BorshDeserialize,
+    A: SingleAccount + SignerSeeds,
+    P: GetProgramId,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        A::signer_seeds(&self.account, buffer)
+    }
+}
+impl<T, A, P, E, C> SupportsConstraint<C> for BorshAccount<T, A, P, E>
 where
     T: AccountData + BorshSerialize + BorshDeserialize,
     A: SingleAccount + SupportsConstraint<C>,
     P: GetProgramId,
+    C: AccountInfoConstraint,
 {
     #[inline]
     fn early_validation(
@@ -173,3 +299,141 @@ where
         A::cleanup(&mut self.account, constraint, context)
     }
 }
+impl_supports_constraint_tuples!(
+    impl[T, A, P, E,] BorshAccount<T, A, P, E>
+    where [
+        T: AccountData + BorshSerialize + BorshDeserialize,
+        A: SingleAccount,
+        P: GetProgramId,
+    ]
+);
+impl<T, A, P, E, F, K, G> SupportsConstraint<HasOne<F, K, G>> for BorshAccount<T, A, P, E>
+where
+    T: AccountData + BorshSerialize + BorshDeserialize,
+    A: SingleAccount,
+    P: GetProgramId,
+    HasOne<F, K, G>: DataConstraint<T>,
+{
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut HasOne<F, K, G>,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        constraint.validate_data(&self.data, context)
+    }
+}
+impl<T, A, P, E, F, V, G> SupportsConstraint<FieldCmp<F, V, G>> for BorshAccount<T, A, P, E>
+where
+    T: AccountData + BorshSerialize + BorshDeserialize,
+    A: SingleAccount,
+    P: GetProgramId,
+    FieldCmp<F, V, G>: DataConstraint<T>,
+{
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut FieldCmp<F, V, G>,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        constraint.validate_data(&self.data, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::constrained::Constrained;
+    use crate::accounts::mutable::Mut;
+    use crate::constraints::address::Address;
+    use crate::test_util::TestAccount;
+    use borsh::io::{Read, Result, Write};
+    use core::iter::once;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio::pubkey::Pubkey;
+
+    struct Vault {
+        authority: Pubkey,
+    }
+    impl AccountData for Vault {
+        const DISCRIMINANT: &'static [u8] = &[7];
+    }
+    impl BorshSerialize for Vault {
+        fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+            self.authority.serialize(writer)
+        }
+    }
+    impl BorshDeserialize for Vault {
+        fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+            Ok(Self {
+                authority: Pubkey::deserialize_reader(reader)?,
+            })
+        }
+    }
+
+    fn vault_data(authority: Pubkey) -> [u8; 33] {
+        let mut data = [0; 33];
+        data[0] = 7;
+        data[1..].copy_from_slice(&authority);
+        data
+    }
+
+    #[test]
+    fn read_only_data_is_not_borrowed() {
+        let mut read_only = TestAccount::new([1; 32])
+            .data(&vault_data([2; 32]))
+            .data_borrowed_mut();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let vault = BorshAccount::<Vault, ReadOnly>::try_accounts(
+            &mut context,
+            &mut once(read_only.info()),
+            (),
+        )
+        .unwrap();
+        assert_eq!(vault.authority, [2; 32]);
+
+        let mut writable = TestAccount::new([1; 32])
+            .writable()
+            .data(&vault_data([2; 32]))
+            .data_borrowed_mut();
+        let error =
+            BorshAccount::<Vault, Mut>::try_accounts(&mut context, &mut once(writable.info()), ())
+                .err()
+                .unwrap();
+        assert_eq!(error.error, ProgramError::AccountBorrowFailed);
+    }
+
+    type Authority = fn(&Vault) -> &Pubkey;
+    type VaultConstraints = (HasOne<Authority>, Address);
+
+    fn assert_decodable<T: DecodeAccounts<(C, ())>, C>() {}
+
+    #[test]
+    fn data_and_account_info_constraints_in_a_tuple() {
+        assert_decodable::<Constrained<BorshAccount<Vault>, VaultConstraints>, VaultConstraints>();
+
+        let mut account = TestAccount::new([1; 32])
+            .owner([9; 32])
+            .data(&vault_data([2; 32]));
+        let mut context = AccountsContext::new(&[9; 32]);
+        let mut vault =
+            BorshAccount::<Vault>::try_accounts(&mut context, &mut once(account.info()), ())
+                .unwrap();
+
+        let authority: Authority = |vault| &vault.authority;
+        let mut passing = (HasOne::new(authority, [2; 32]), Address([1; 32]));
+        assert_eq!(vault.late_validation(&mut passing, &mut context), Ok(()));
+
+        let mut wrong_authority = (HasOne::new(authority, [3; 32]), Address([1; 32]));
+        assert_eq!(
+            vault.late_validation(&mut wrong_authority, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintHasOne).with_pubkeys([2; 32], [3; 32]))
+        );
+
+        let mut wrong_address = (HasOne::new(authority, [2; 32]), Address([4; 32]));
+        assert_eq!(
+            vault.late_validation(&mut wrong_address, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintAddress).with_pubkeys([1; 32], [4; 32]))
+        );
+    }
+}
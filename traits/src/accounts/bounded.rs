@@ -0,0 +1,190 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, CleanupAccounts, DecodeAccounts, ValidateAccounts};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use alloc::vec::Vec;
+use core::panic::Location;
+use derive_more::{Deref, DerefMut};
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+
+/// Decodes `T` greedily while accounts remain, up to `MAX` times, leaving the others for later
+/// fields. Fails if fewer than `MIN` could be decoded.
+#[derive(Clone, Debug, Deref, DerefMut)]
+pub struct Bounded<T, const MIN: usize, const MAX: usize>(pub Vec<T>);
+impl<T, const MIN: usize, const MAX: usize> Accounts for Bounded<T, MIN, MAX>
+where
+    T: Accounts,
+{
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        self.iter().flat_map(T::to_account_infos)
+    }
+
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        self.iter()
+            .flat_map(move |a| T::to_account_metas(a, is_signer))
+    }
+}
+impl<T, const MIN: usize, const MAX: usize, A> DecodeAccounts<A> for Bounded<T, MIN, MAX>
+where
+    T: DecodeAccounts<A>,
+    A: Clone,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: A,
+    ) -> AnchorResult<Self> {
+        const { assert!(MIN <= MAX, "`MIN` is greater than `MAX`") };
+        let location = Location::caller();
+        let mut accounts = accounts.peekable();
+
+        let mut out = Vec::with_capacity(MIN);
+        while out.len() < MAX && accounts.peek().is_some() {
+            out.push(T::try_accounts(
+                accounts_context,
+                &mut accounts,
+                arg.clone(),
+            )?);
+        }
+        if out.len() < MIN {
+            return Err(AnchorError::from(ErrorCode::AccountNotEnoughKeys).logged(location, None));
+        }
+
+        Ok(Self(out))
+    }
+
+    fn size_hint() -> (usize, Option<usize>) {
+        let t_size_hint = T::size_hint();
+        (
+            t_size_hint.0.saturating_mul(MIN),
+            t_size_hint.1.and_then(|v| v.checked_mul(MAX)),
+        )
+    }
+}
+impl<T, const MIN: usize, const MAX: usize, A> ValidateAccounts<A> for Bounded<T, MIN, MAX>
+where
+    T: ValidateAccounts<A>,
+    A: Clone,
+{
+    fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::validate(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, const MIN: usize, const MAX: usize, A> CleanupAccounts<A> for Bounded<T, MIN, MAX>
+where
+    T: CleanupAccounts<A>,
+    A: Clone,
+{
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, const MIN: usize, const MAX: usize> SignerSeeds for Bounded<T, MIN, MAX>
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self.iter() {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
+impl<T, const MIN: usize, const MAX: usize, C> SupportsConstraint<C> for Bounded<T, MIN, MAX>
+where
+    T: SupportsConstraint<C>,
+{
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::early_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::late_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, constraint, context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    fn decode<const MIN: usize, const MAX: usize>(
+        accounts: &mut [TestAccount],
+    ) -> (AnchorResult<Bounded<AccountInfo, MIN, MAX>>, usize) {
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut infos = accounts.iter_mut().map(TestAccount::info);
+        let result = Bounded::try_accounts(&mut context, &mut infos, ());
+        (result, infos.count())
+    }
+
+    #[test]
+    fn decodes_up_to_max() {
+        let mut accounts = [1, 2, 3, 4].map(|key| TestAccount::new([key; 32]));
+        let (result, remaining) = decode::<1, 3>(&mut accounts);
+        let keys: Vec<_> = result.unwrap().iter().map(|info| *info.key()).collect();
+        assert_eq!(keys, [[1; 32], [2; 32], [3; 32]]);
+        assert_eq!(remaining, 1);
+
+        let (result, remaining) = decode::<1, 4>(&mut accounts);
+        assert_eq!(result.unwrap().len(), 4);
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn requires_min() {
+        let mut accounts = [1, 2].map(|key| TestAccount::new([key; 32]));
+        assert_eq!(decode::<2, 3>(&mut accounts).0.unwrap().len(), 2);
+        assert_eq!(
+            decode::<3, 3>(&mut accounts)
+                .0
+                .map_err(|error| error.error)
+                .unwrap_err(),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+        assert!(decode::<0, 3>(&mut []).0.unwrap().is_empty());
+    }
+
+    #[test]
+    fn size_hint_saturates() {
+        assert_eq!(
+            <Bounded<AccountInfo, 1, 3> as DecodeAccounts<()>>::size_hint(),
+            (1, Some(3))
+        );
+        assert_eq!(
+            <Bounded<Bounded<AccountInfo, { usize::MAX }, { usize::MAX }>, 2, 2> as DecodeAccounts<()>>::size_hint(),
+            (usize::MAX, None)
+        );
+    }
+}
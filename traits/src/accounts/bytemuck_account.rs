@@ -1,21 +1,27 @@
 use crate::accounts::mutable::ReadOnly;
-use crate::error::{AnchorError, AnchorResult};
+use crate::constraints::all::impl_supports_constraint_tuples;
+use crate::constraints::field_cmp::FieldCmp;
+use crate::constraints::has_one::HasOne;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::account_data::AccountData;
-use crate::traits::constraint::SupportsConstraint;
+use crate::traits::constraint::{AccountInfoConstraint, DataConstraint, SupportsConstraint};
 use crate::traits::maybe_bool::{False, MaybeBool, True};
 use crate::traits::program::{CurrentProgram, GetProgramId};
+use crate::traits::seeds::{DataSeeds, StoredBump};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use bytemuck::{CheckedBitPattern, NoUninit};
 use core::marker::PhantomData;
+use core::panic::Location;
 use derive_where::derive_where;
 use pinocchio::account_info::{AccountInfo, Ref, RefMut};
 use pinocchio::instruction::AccountMeta;
 
 #[derive_where(Clone; T: Clone, A: Clone)]
-pub struct BytemuckAccount<T, A = ReadOnly<AccountInfo>, P = CurrentProgram>
+pub struct BytemuckAccount<T, A = ReadOnly<AccountInfo>, P = CurrentProgram, E = ()>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount,
@@ -24,18 +30,18 @@ where
     account: A,
     _data: PhantomData<fn() -> T>,
     _program: PhantomData<fn() -> P>,
+    _error: PhantomData<fn() -> E>,
 }
-impl<T, A, P> BytemuckAccount<T, A, P>
+impl<T, A, P, E> BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount,
     P: GetProgramId,
+    E: CustomErrorGenerator + Default,
 {
     pub fn data(&self) -> AnchorResult<Ref<'_, T>> {
-        Ref::try_map(self.account_info_ref().try_borrow_data()?, |data| {
-            bytemuck::checked::try_from_bytes(&data[T::DISCRIMINANT.len()..])
-        })
-        .map_err(|_| AnchorError::InvalidAccountData)
+        Ref::try_map(self.account_info_ref().try_borrow_data()?, from_bytes)
+            .map_err(|(_, error)| E::default().generate(error.into()))
     }
 
     pub fn data_mut(&mut self) -> AnchorResult<RefMut<'_, T>>
@@ -43,10 +49,11 @@ where
         T: NoUninit,
         A: SingleAccount<Mutable = True>,
     {
-        RefMut::try_map(self.account_info_ref().try_borrow_mut_data()?, |data| {
-            bytemuck::checked::try_from_bytes_mut(&mut data[T::DISCRIMINANT.len()..])
-        })
-        .map_err(|_| AnchorError::InvalidAccountData)
+        RefMut::try_map(
+            self.account_info_ref().try_borrow_mut_data()?,
+            from_bytes_mut,
+        )
+        .map_err(|(_, error)| E::default().generate(error.into()))
     }
 
     pub fn data_readonly(&self) -> AnchorResult<&'_ T>
@@ -54,11 +61,62 @@ where
         A: SingleAccount<Mutable = False>,
     {
         let data = unsafe { self.account.account_info_ref().borrow_data_unchecked() };
-        bytemuck::checked::try_from_bytes(&data[T::DISCRIMINANT.len()..])
-            .map_err(|_| AnchorError::InvalidAccountData)
+        from_bytes(data).map_err(|error| E::default().generate(error.into()))
+    }
+
+    /// The error if the data does not start with the discriminant of `T`.
+    fn discriminant_error(&self) -> AnchorResult<Option<ErrorCode>> {
+        let check_discriminant = |data: &[u8]| {
+            if data.len() < T::DISCRIMINANT.len() {
+                Some(ErrorCode::AccountDiscriminatorNotFound)
+            } else if &data[..T::DISCRIMINANT.len()] != T::DISCRIMINANT {
+                Some(ErrorCode::AccountDiscriminatorMismatch)
+            } else {
+                None
+            }
+        };
+
+        Ok(if const { A::Mutable::IS_FALSE } {
+            // If readonly we can directly access the data safely.
+            check_discriminant(unsafe { self.account.account_info_ref().borrow_data_unchecked() })
+        } else {
+            check_discriminant(&self.account.account_info_ref().try_borrow_data()?)
+        })
+    }
+
+    /// The data checked by data constraints. `None` with a wrong discriminant in collect-all
+    /// mode, where validation already reported it and the data is not `T`.
+    fn constrained_data(&self, context: &AccountsContext) -> AnchorResult<Option<Ref<'_, T>>> {
+        match self.discriminant_error()? {
+            None => self.data().map(Some),
+            Some(_) if context.is_collecting_all() => Ok(None),
+            Some(error) => Err(E::default().generate(error.into())),
+        }
     }
 }
-impl<T, A, P> Accounts for BytemuckAccount<T, A, P>
+
+/// The data of `T` after its discriminant, which is not checked.
+fn from_bytes<T>(data: &[u8]) -> Result<&T, ErrorCode>
+where
+    T: AccountData + CheckedBitPattern,
+{
+    let data = data
+        .get(T::DISCRIMINANT.len()..)
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+    bytemuck::checked::try_from_bytes(data).map_err(|_| ErrorCode::AccountDidNotDeserialize)
+}
+
+fn from_bytes_mut<T>(data: &mut [u8]) -> Result<&mut T, ErrorCode>
+where
+    T: AccountData + CheckedBitPattern + NoUninit,
+{
+    let data = data
+        .get_mut(T::DISCRIMINANT.len()..)
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+    bytemuck::checked::try_from_bytes_mut(data).map_err(|_| ErrorCode::AccountDidNotDeserialize)
+}
+
+impl<T, A, P, E> Accounts for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount,
@@ -74,7 +132,7 @@ where
         A::to_account_infos(&self.account)
     }
 }
-unsafe impl<T, A, P> SingleAccount for BytemuckAccount<T, A, P>
+unsafe impl<T, A, P, E> SingleAccount for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount,
@@ -88,7 +146,7 @@ where
         A::account_info_ref(&self.account)
     }
 }
-impl<T, A, P, Arg> DecodeAccounts<Arg> for BytemuckAccount<T, A, P>
+impl<T, A, P, E, Arg> DecodeAccounts<Arg> for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount + DecodeAccounts<Arg>,
@@ -103,6 +161,7 @@ where
             account: A::try_accounts(accounts_context, accounts, arg)?,
             _data: PhantomData,
             _program: PhantomData,
+            _error: PhantomData,
         })
     }
 
@@ -111,38 +170,39 @@ where
         A::size_hint()
     }
 }
-impl<T, A, P, Arg> ValidateAccounts<Arg> for BytemuckAccount<T, A, P>
+impl<T, A, P, E, Arg> ValidateAccounts<Arg> for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount + ValidateAccounts<Arg>,
     P: GetProgramId,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: Arg) -> AnchorResult {
-        if self.account.owner() != P::program_id(accounts_context) {
-            return Err(AnchorError::InvalidAccountOwner);
+        let location = Location::caller();
+        let program_id = P::program_id(accounts_context);
+        if self.account.owner() != program_id {
+            accounts_context.report::<Self>(
+                E::default().generate(
+                    AnchorError::from(ErrorCode::AccountOwnedByWrongProgram)
+                        .with_pubkeys(*self.account.owner(), *program_id),
+                ),
+                location,
+                Some(self.account.key()),
+            )?;
         }
 
-        let check_discriminant = |data: &[u8]| {
-            if data.len() < T::DISCRIMINANT.len()
-                || &data[..T::DISCRIMINANT.len()] != T::DISCRIMINANT
-            {
-                Err(AnchorError::InvalidAccountData)
-            } else {
-                Ok(())
-            }
-        };
-
-        if const { A::Mutable::IS_FALSE } {
-            // If readonly we can directly access the data safely.
-            check_discriminant(unsafe { self.account.account_info_ref().borrow_data_unchecked() })?;
-        } else {
-            check_discriminant(&self.account.account_info_ref().try_borrow_data()?)?;
+        if let Some(error) = self.discriminant_error()? {
+            accounts_context.report::<Self>(
+                E::default().generate(error.into()),
+                location,
+                Some(self.account.key()),
+            )?;
         }
 
         A::validate(&mut self.account, accounts_context, arg)
     }
 }
-impl<T, A, P, Arg> CleanupAccounts<Arg> for BytemuckAccount<T, A, P>
+impl<T, A, P, E, Arg> CleanupAccounts<Arg> for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount + CleanupAccounts<Arg>,
@@ -153,11 +213,52 @@ where
         A::cleanup(&mut self.account, accounts_context, arg)
     }
 }
-impl<T, A, P, C> SupportsConstraint<C> for BytemuckAccount<T, A, P>
+impl<T, A, P, E> StoredBump for BytemuckAccount<T, A, P, E>
+where
+    T: AccountData + CheckedBitPattern + StoredBump,
+    A: SingleAccount,
+    P: GetProgramId,
+    E: CustomErrorGenerator + Default,
+{
+    #[inline]
+    fn stored_bump(&self) -> AnchorResult<u8> {
+        T::stored_bump(&*self.data()?)
+    }
+}
+impl<T, A, P, E> DataSeeds for BytemuckAccount<T, A, P, E>
+where
+    T: AccountData + CheckedBitPattern + DataSeeds,
+    A: SingleAccount,
+    P: GetProgramId,
+    E: CustomErrorGenerator + Default,
+{
+    type Seeds = T::Seeds;
+
+    #[inline]
+    fn data_seeds(&self, context: &AccountsContext) -> AnchorResult<Self::Seeds> {
+        T::data_seeds(&*self.data()?, context)
+    }
+}
+impl<T, A, P, E> SignerSeeds for BytemuckAccount<T, A, P, E>
+where
+    T: AccountData + CheckedBitPattern,
+    A: SingleAccount + SignerSeeds,
+    P: GetProgramId,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        A::signer_seeds(&self.account, buffer)
+    }
+}
+impl<T, A, P, E, C> SupportsConstraint<C> for BytemuckAccount<T, A, P, E>
 where
     T: AccountData + CheckedBitPattern,
     A: SingleAccount + SupportsConstraint<C>,
     P: GetProgramId,
+    C: AccountInfoConstraint,
 {
     fn early_validation(
         &mut self,
@@ -179,3 +280,184 @@ where
         A::cleanup(&mut self.account, constraint, context)
     }
 }
+impl_supports_constraint_tuples!(
+    impl[T, A, P, E,] BytemuckAccount<T, A, P, E>
+    where [
+        T: AccountData + CheckedBitPattern,
+        A: SingleAccount,
+        P: GetProgramId,
+    ]
+);
+impl<T, A, P, E, F, K, G> SupportsConstraint<HasOne<F, K, G>> for BytemuckAccount<T, A, P, E>
+where
+    T: AccountData + CheckedBitPattern,
+    A: SingleAccount,
+    P: GetProgramId,
+    E: CustomErrorGenerator + Default,
+    HasOne<F, K, G>: DataConstraint<T>,
+{
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut HasOne<F, K, G>,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        match self.constrained_data(context)? {
+            Some(data) => constraint.validate_data(&data, context),
+            None => Ok(()),
+        }
+    }
+}
+impl<T, A, P, E, F, V, G> SupportsConstraint<FieldCmp<F, V, G>> for BytemuckAccount<T, A, P, E>
+where
+    T: AccountData + CheckedBitPattern,
+    A: SingleAccount,
+    P: GetProgramId,
+    E: CustomErrorGenerator + Default,
+    FieldCmp<F, V, G>: DataConstraint<T>,
+{
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut FieldCmp<F, V, G>,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        match self.constrained_data(context)? {
+            Some(data) => constraint.validate_data(&data, context),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::mutable::Mut;
+    use crate::test_util::TestAccount;
+    use bytemuck::{Pod, Zeroable};
+    use core::iter::once;
+    use pinocchio::pubkey::Pubkey;
+
+    #[derive(Copy, Clone)]
+    #[repr(C)]
+    struct Vault {
+        bump: u8,
+        authority: Pubkey,
+    }
+    // Safety: `Vault` is only bytes, so has no padding and any bit pattern is valid.
+    unsafe impl Zeroable for Vault {}
+    unsafe impl Pod for Vault {}
+    impl AccountData for Vault {
+        const DISCRIMINANT: &'static [u8] = &[7, 7];
+    }
+    impl StoredBump for Vault {
+        fn stored_bump(&self) -> AnchorResult<u8> {
+            Ok(self.bump)
+        }
+    }
+    impl DataSeeds for Vault {
+        type Seeds = (Pubkey,);
+
+        fn data_seeds(&self, _context: &AccountsContext) -> AnchorResult<Self::Seeds> {
+            Ok((self.authority,))
+        }
+    }
+
+    #[test]
+    fn short_data_is_an_error() {
+        let mut context = AccountsContext::new(&[9; 32]);
+        for (data, error) in [
+            (&[][..], ErrorCode::AccountDiscriminatorNotFound),
+            (&[7][..], ErrorCode::AccountDiscriminatorNotFound),
+            (&[7, 7, 1][..], ErrorCode::AccountDidNotDeserialize),
+        ] {
+            let mut account = TestAccount::new([1; 32]).owner([9; 32]).data(data);
+            let vault =
+                BytemuckAccount::<Vault>::try_accounts(&mut context, &mut once(account.info()), ())
+                    .unwrap();
+            assert_eq!(vault.data().err(), Some(error.into()));
+            assert_eq!(vault.data_readonly().err(), Some(error.into()));
+            assert_eq!(vault.stored_bump(), Err(error.into()));
+            assert_eq!(vault.data_seeds(&context), Err(error.into()));
+
+            let mut writable = TestAccount::new([1; 32])
+                .owner([9; 32])
+                .writable()
+                .data(data);
+            let mut vault = BytemuckAccount::<Vault, Mut>::try_accounts(
+                &mut context,
+                &mut once(writable.info()),
+                (),
+            )
+            .unwrap();
+            assert_eq!(vault.data_mut().err(), Some(error.into()));
+        }
+    }
+
+    #[test]
+    fn reads_data_after_discriminant() {
+        let mut data = [0; 35];
+        data[..2].copy_from_slice(&[7, 7]);
+        data[2] = 254;
+        data[3..].copy_from_slice(&[2; 32]);
+        let mut account = TestAccount::new([1; 32]).owner([9; 32]).data(&data);
+        let mut context = AccountsContext::new(&[9; 32]);
+        let vault =
+            BytemuckAccount::<Vault>::try_accounts(&mut context, &mut once(account.info()), ())
+                .unwrap();
+        assert_eq!(vault.data_readonly().unwrap().authority, [2; 32]);
+        assert_eq!(vault.stored_bump(), Ok(254));
+        assert_eq!(vault.data_seeds(&context), Ok(([2; 32],)));
+    }
+
+    type Authority = fn(&Vault) -> &Pubkey;
+    type Bump = fn(&Vault) -> &u8;
+
+    #[test]
+    fn data_constraints_check_discriminant() {
+        let mut account = TestAccount::new([1; 32]).owner([9; 32]).data(&[0; 35]);
+        let mut context = AccountsContext::new(&[9; 32]);
+        let mut vault =
+            BytemuckAccount::<Vault>::try_accounts(&mut context, &mut once(account.info()), ())
+                .unwrap();
+        let authority: Authority = |vault| &vault.authority;
+        assert_eq!(
+            vault.late_validation(&mut HasOne::new(authority, [0; 32]), &mut context),
+            Err(ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+        let bump: Bump = |vault| &vault.bump;
+        assert_eq!(
+            vault.late_validation(&mut FieldCmp::eq(bump, 0), &mut context),
+            Err(ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collect_all_reports_only_the_discriminant() {
+        use crate::accounts::constrained::Constrained;
+
+        let authority: Authority = |vault| &vault.authority;
+        let bump: Bump = |vault| &vault.bump;
+        for (data, error) in [
+            (&[][..], ErrorCode::AccountDiscriminatorNotFound),
+            (&[0; 35][..], ErrorCode::AccountDiscriminatorMismatch),
+        ] {
+            let mut account = TestAccount::new([1; 32]).owner([9; 32]).data(data);
+            let mut context = AccountsContext::new(&[9; 32]);
+            let (result, diagnostics) = context.collect_all(|context| {
+                let mut vault = Constrained::<
+                    BytemuckAccount<Vault>,
+                    (HasOne<Authority>, FieldCmp<Bump, u8>),
+                >::try_accounts(
+                    context,
+                    &mut once(account.info()),
+                    ((HasOne::new(authority, [2; 32]), FieldCmp::eq(bump, 1)), ()),
+                )?;
+                vault.validate(context, ())
+            });
+            assert_eq!(result, Err(error.into()));
+            assert_eq!(diagnostics.failures.len(), 1);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use crate::error::{AnchorError, AnchorResult};
+use crate::traits::account::{
+    Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
+};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use core::panic::Location;
+use derive_more::{Deref, DerefMut};
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+
+/// Logs a constraint error on the first account of `account`.
+#[inline]
+fn logged_on(error: AnchorError, location: &Location<'_>, account: &impl Accounts) -> AnchorError {
+    error.logged(
+        location,
+        account
+            .to_account_infos()
+            .next()
+            .as_ref()
+            .map(AccountInfo::key),
+    )
+}
+
+/// Runs the [`SupportsConstraint`] hooks of `T` for `C` alongside the account lifecycle.
+///
+/// Decoded with `(C, A)` where `A` is the decode arg of `T`, or `()` if `C` is [`Default`].
+#[derive(Copy, Clone, Debug, Deref, DerefMut)]
+pub struct Constrained<T, C> {
+    #[deref]
+    #[deref_mut]
+    pub account: T,
+    pub constraint: C,
+}
+impl<T, C> Accounts for Constrained<T, C>
+where
+    T: Accounts,
+{
+    #[inline]
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        T::to_account_metas(&self.account, is_signer)
+    }
+
+    #[inline]
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        T::to_account_infos(&self.account)
+    }
+}
+unsafe impl<T, C> SingleAccount for Constrained<T, C>
+where
+    T: SingleAccount,
+{
+    type Mutable = T::Mutable;
+    type CanSign = T::CanSign;
+
+    #[inline]
+    fn account_info_ref(&self) -> &AccountInfo {
+        T::account_info_ref(&self.account)
+    }
+}
+impl<T, C, A> DecodeAccounts<(C, A)> for Constrained<T, C>
+where
+    T: DecodeAccounts<A> + SupportsConstraint<C>,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: (C, A),
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let (mut constraint, arg) = arg;
+        let mut account = T::try_accounts(accounts_context, accounts, arg)?;
+        if let Err(error) = account.early_validation(&mut constraint, accounts_context) {
+            let info = account.to_account_infos().next();
+            accounts_context.report::<Self>(
+                error,
+                location,
+                info.as_ref().map(AccountInfo::key),
+            )?;
+        }
+        Ok(Self {
+            account,
+            constraint,
+        })
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        T::size_hint()
+    }
+}
+impl<T, C> DecodeAccounts<()> for Constrained<T, C>
+where
+    T: DecodeAccounts<()> + SupportsConstraint<C>,
+    C: Default,
+{
+    #[inline]
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        _arg: (),
+    ) -> AnchorResult<Self> {
+        Self::try_accounts(accounts_context, accounts, (C::default(), ()))
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        T::size_hint()
+    }
+}
+impl<T, C, A> ValidateAccounts<A> for Constrained<T, C>
+where
+    T: ValidateAccounts<A> + SupportsConstraint<C>,
+{
+    fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        let location = Location::caller();
+        T::validate(&mut self.account, accounts_context, arg)?;
+        match self
+            .account
+            .late_validation(&mut self.constraint, accounts_context)
+        {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                let info = self.account.to_account_infos().next();
+                accounts_context.report::<Self>(
+                    error,
+                    location,
+                    info.as_ref().map(AccountInfo::key),
+                )
+            }
+        }
+    }
+}
+impl<T, C, A> CleanupAccounts<A> for Constrained<T, C>
+where
+    T: CleanupAccounts<A> + SupportsConstraint<C>,
+{
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        let location = Location::caller();
+        SupportsConstraint::cleanup(&mut self.account, &mut self.constraint, accounts_context)
+            .map_err(|error| logged_on(error, location, &self.account))?;
+        CleanupAccounts::cleanup(&mut self.account, accounts_context, arg)
+    }
+}
+impl<T, C> SignerSeeds for Constrained<T, C>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        T::signer_seeds(&self.account, buffer)
+    }
+}
+impl<T, C, C2> SupportsConstraint<C2> for Constrained<T, C>
+where
+    T: SupportsConstraint<C2>,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        constraint: &mut C2,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        T::early_validation(&mut self.account, constraint, context)
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut C2,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        T::late_validation(&mut self.account, constraint, context)
+    }
+
+    #[inline]
+    fn cleanup(&mut self, constraint: &mut C2, context: &mut AccountsContext) -> AnchorResult {
+        T::cleanup(&mut self.account, constraint, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_util::TestAccount;
+    use core::cell::Cell;
+    use core::iter::once;
+
+    /// The lifecycle events of a [`Recorded`] account and its constraint.
+    #[derive(Default)]
+    struct Log {
+        events: [Cell<&'static str>; 8],
+        len: Cell<usize>,
+    }
+    impl Log {
+        /// Records `event`, failing if it is `fail`.
+        fn record(&self, event: &'static str, fail: &str) -> AnchorResult {
+            self.events[self.len.get()].set(event);
+            self.len.set(self.len.get() + 1);
+            if event == fail {
+                Err(ErrorCode::ConstraintRaw.into())
+            } else {
+                Ok(())
+            }
+        }
+
+        fn events(&self) -> impl Iterator<Item = &'static str> + '_ {
+            self.events[..self.len.get()].iter().map(Cell::get)
+        }
+    }
+
+    /// Records its lifecycle and that of a [`Recorder`] constraint, failing at `fail`.
+    struct Recorded<'a> {
+        info: AccountInfo,
+        log: &'a Log,
+        fail: &'static str,
+    }
+    struct Recorder;
+    impl Accounts for Recorded<'_> {
+        fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+            once(self.info)
+        }
+
+        fn to_account_metas(
+            &self,
+            is_signer: Option<bool>,
+        ) -> impl Iterator<Item = AccountMeta<'_>> {
+            self.info.to_account_metas(is_signer)
+        }
+    }
+    impl<'a> DecodeAccounts<(&'a Log, &'static str)> for Recorded<'a> {
+        fn try_accounts(
+            accounts_context: &mut AccountsContext,
+            accounts: &mut impl Iterator<Item = AccountInfo>,
+            (log, fail): (&'a Log, &'static str),
+        ) -> AnchorResult<Self> {
+            let info = AccountInfo::try_accounts(accounts_context, accounts, ())?;
+            log.record("decode", fail)?;
+            Ok(Self { info, log, fail })
+        }
+    }
+    impl ValidateAccounts<()> for Recorded<'_> {
+        fn validate(&mut self, _accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
+            self.log.record("validate", self.fail)
+        }
+    }
+    impl CleanupAccounts<()> for Recorded<'_> {
+        fn cleanup(&mut self, _accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
+            self.log.record("cleanup", self.fail)
+        }
+    }
+    impl SupportsConstraint<Recorder> for Recorded<'_> {
+        fn early_validation(
+            &mut self,
+            _constraint: &mut Recorder,
+            _context: &mut AccountsContext,
+        ) -> AnchorResult {
+            self.log.record("early", self.fail)
+        }
+
+        fn late_validation(
+            &mut self,
+            _constraint: &mut Recorder,
+            _context: &mut AccountsContext,
+        ) -> AnchorResult {
+            self.log.record("late", self.fail)
+        }
+
+        fn cleanup(
+            &mut self,
+            _constraint: &mut Recorder,
+            _context: &mut AccountsContext,
+        ) -> AnchorResult {
+            self.log.record("constraint cleanup", self.fail)
+        }
+    }
+
+    /// Runs the lifecycle of a constrained [`Recorded`] failing at `fail`, returning the result.
+    fn run(log: &Log, fail: &'static str) -> AnchorResult {
+        let mut account = TestAccount::new([1; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut constrained = Constrained::<Recorded, Recorder>::try_accounts(
+            &mut context,
+            &mut once(account.info()),
+            (Recorder, (log, fail)),
+        )?;
+        constrained.validate(&mut context, ())?;
+        CleanupAccounts::cleanup(&mut constrained, &mut context, ())
+    }
+
+    #[test]
+    fn runs_constraint_hooks_in_order() {
+        let log = Log::default();
+        assert_eq!(run(&log, ""), Ok(()));
+        assert!(log.events().eq([
+            "decode",
+            "early",
+            "validate",
+            "late",
+            "constraint cleanup",
+            "cleanup"
+        ]));
+    }
+
+    #[test]
+    fn propagates_errors_of_each_phase() {
+        let cases: [(&str, &[&str]); 5] = [
+            ("early", &["decode", "early"]),
+            ("validate", &["decode", "early", "validate"]),
+            ("late", &["decode", "early", "validate", "late"]),
+            (
+                "constraint cleanup",
+                &["decode", "early", "validate", "late", "constraint cleanup"],
+            ),
+            (
+                "cleanup",
+                &[
+                    "decode",
+                    "early",
+                    "validate",
+                    "late",
+                    "constraint cleanup",
+                    "cleanup",
+                ],
+            ),
+        ];
+        for (fail, events) in cases {
+            let log = Log::default();
+            assert_eq!(
+                run(&log, fail).map_err(|error| error.error),
+                Err(ErrorCode::ConstraintRaw.into()),
+                "failing at {fail}"
+            );
+            assert!(log.events().eq(events.iter().copied()), "failing at {fail}");
+        }
+    }
+}
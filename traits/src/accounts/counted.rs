@@ -0,0 +1,214 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, CleanupAccounts, DecodeAccounts, ValidateAccounts};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use alloc::vec::Vec;
+use core::panic::Location;
+use derive_more::{Deref, DerefMut};
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+
+/// A list of `T` whose length is given by the decode arg, e.g. from an instruction field, so it
+/// can be followed by other accounts.
+///
+/// Decodes with `count` or `(count, arg)`. Fails if `count` is more than the accounts left, so a
+/// `T` that takes no accounts is decoded at most once per remaining account.
+#[derive(Clone, Debug, Deref, DerefMut)]
+pub struct Counted<T>(pub Vec<T>);
+impl<T> Accounts for Counted<T>
+where
+    T: Accounts,
+{
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        self.iter().flat_map(T::to_account_infos)
+    }
+
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        self.iter()
+            .flat_map(move |a| T::to_account_metas(a, is_signer))
+    }
+}
+impl<T, A> DecodeAccounts<(usize, A)> for Counted<T>
+where
+    T: DecodeAccounts<A>,
+    A: Clone,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: (usize, A),
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let (count, arg) = arg;
+        // `count` may come from instruction data, so is not trusted for the allocation.
+        if accounts
+            .size_hint()
+            .1
+            .is_some_and(|remaining| count > remaining)
+        {
+            return Err(AnchorError::from(ErrorCode::AccountNotEnoughKeys).logged(location, None));
+        }
+        let mut out = Vec::with_capacity(count.min(accounts.size_hint().0));
+        for _ in 0..count {
+            out.push(T::try_accounts(accounts_context, accounts, arg.clone())?);
+        }
+        Ok(Self(out))
+    }
+
+    /// The count is only known at runtime.
+    fn size_hint() -> (usize, Option<usize>) {
+        match T::size_hint() {
+            (_, Some(0)) => (0, Some(0)),
+            _ => (0, None),
+        }
+    }
+}
+impl<T> DecodeAccounts<usize> for Counted<T>
+where
+    T: DecodeAccounts<()>,
+{
+    #[inline]
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: usize,
+    ) -> AnchorResult<Self> {
+        Self::try_accounts(accounts_context, accounts, (arg, ()))
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        <Self as DecodeAccounts<(usize, ())>>::size_hint()
+    }
+}
+impl<T, A> ValidateAccounts<A> for Counted<T>
+where
+    T: ValidateAccounts<A>,
+    A: Clone,
+{
+    fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::validate(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, A> CleanupAccounts<A> for Counted<T>
+where
+    T: CleanupAccounts<A>,
+    A: Clone,
+{
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T> SignerSeeds for Counted<T>
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self.iter() {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
+impl<T, C> SupportsConstraint<C> for Counted<T>
+where
+    T: SupportsConstraint<C>,
+{
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::early_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::late_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, constraint, context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{NoAccounts, TestAccount};
+
+    #[test]
+    fn decodes_count() {
+        let mut accounts = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut infos = accounts.iter_mut().map(TestAccount::info);
+
+        let counted: Counted<AccountInfo> =
+            Counted::try_accounts(&mut context, &mut infos, 2).unwrap();
+        let keys: Vec<_> = counted.iter().map(|info| *info.key()).collect();
+        assert_eq!(keys, [[1; 32], [2; 32]]);
+        assert_eq!(infos.next().map(|info| *info.key()), Some([3; 32]));
+
+        let counted: Counted<AccountInfo> =
+            Counted::try_accounts(&mut context, &mut infos, 0).unwrap();
+        assert!(counted.is_empty());
+    }
+
+    #[test]
+    fn fails_with_fewer_accounts_than_count() {
+        let mut accounts = [TestAccount::new([1; 32])];
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut infos = accounts.iter_mut().map(TestAccount::info);
+        assert_eq!(
+            <Counted<AccountInfo>>::try_accounts(&mut context, &mut infos, usize::MAX)
+                .map_err(|error| error.error)
+                .unwrap_err(),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+    }
+
+    #[test]
+    fn count_is_bounded_by_remaining_accounts() {
+        let mut accounts = [TestAccount::new([1; 32])];
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut infos = accounts.iter_mut().map(TestAccount::info);
+        assert_eq!(
+            <Counted<NoAccounts>>::try_accounts(&mut context, &mut infos, usize::MAX)
+                .map_err(|error| error.error)
+                .unwrap_err(),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+        let counted = <Counted<NoAccounts>>::try_accounts(&mut context, &mut infos, 1).unwrap();
+        assert_eq!(counted.len(), 1);
+    }
+
+    #[test]
+    fn size_hint_is_unbounded() {
+        assert_eq!(
+            <Counted<AccountInfo> as DecodeAccounts<usize>>::size_hint(),
+            (0, None)
+        );
+    }
+}
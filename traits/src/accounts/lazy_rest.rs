@@ -0,0 +1,329 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, CleanupAccounts, DecodeAccounts, ValidateAccounts};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use crate::util::StackVec;
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+use derive_where::derive_where;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+
+/// The remaining accounts, decoded on demand by [`next`](Self::next) so accounts that aren't
+/// visited are never decoded or validated.
+///
+/// Only visited accounts are part of `self`, e.g. cleaned up and passed to CPIs.
+///
+/// [`DecodeAccounts`] only lends the accounts iterator for the call, so instead of holding it the
+/// remaining accounts are copied inline on decode. `N` caps the remaining accounts, not just the
+/// visited ones: decoding fails with
+/// [`AccountsCapacityExceeded`](ErrorCode::AccountsCapacityExceeded) if more than `N` remain, even
+/// if they would never be visited. Use `Rest` with the `alloc` feature for any number of accounts.
+#[derive_where(Clone; T: Clone)]
+#[derive_where(Debug; T: Debug)]
+pub struct LazyRest<T, const N: usize> {
+    accounts: StackVec<AccountInfo, N>,
+    next_account: usize,
+    visited: StackVec<T, N>,
+}
+impl<T, const N: usize> LazyRest<T, N> {
+    /// Decodes and validates the next `T`, `None` once every account is visited.
+    #[track_caller]
+    pub fn next<D, V>(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        decode_arg: D,
+        validate_arg: V,
+    ) -> Option<AnchorResult<&mut T>>
+    where
+        T: DecodeAccounts<D> + ValidateAccounts<V>,
+    {
+        let location = Location::caller();
+        let remaining = &self.accounts[self.next_account..];
+        let key = *remaining.first()?.key();
+        let mut accounts = remaining.iter().copied();
+        let t = T::try_accounts(accounts_context, &mut accounts, decode_arg);
+        self.next_account = self.accounts.len() - accounts.len();
+
+        let mut t = match t {
+            Ok(t) => t,
+            Err(error) => return Some(Err(error)),
+        };
+        if let Err(error) = T::validate(&mut t, accounts_context, validate_arg) {
+            return Some(Err(error));
+        }
+        if self.visited.push(t).is_err() {
+            // Only if `T` decodes from no accounts.
+            let error = AnchorError::from(ErrorCode::AccountsCapacityExceeded);
+            return Some(
+                accounts_context
+                    .report::<Self>(error, location, Some(&key))
+                    .and(Err(error)),
+            );
+        }
+        self.visited.last_mut().map(Ok)
+    }
+
+    /// The number of accounts not yet visited.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.accounts.len() - self.next_account
+    }
+}
+impl<T, const N: usize> Deref for LazyRest<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.visited
+    }
+}
+impl<T, const N: usize> DerefMut for LazyRest<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.visited
+    }
+}
+impl<T, const N: usize> Accounts for LazyRest<T, N>
+where
+    T: Accounts,
+{
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        self.iter().flat_map(T::to_account_infos)
+    }
+
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        self.iter()
+            .flat_map(move |a| T::to_account_metas(a, is_signer))
+    }
+}
+impl<T, const N: usize> DecodeAccounts<()> for LazyRest<T, N>
+where
+    T: Accounts,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        _arg: (),
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let mut out = StackVec::<AccountInfo, N>::new();
+        for account in accounts {
+            if out.push(account).is_err() {
+                return Err(AnchorError::from(ErrorCode::AccountsCapacityExceeded)
+                    .logged(location, Some(account.key())));
+            }
+        }
+        // Recorded in order now, visiting records them again later which doesn't change their
+        // indices.
+        #[cfg(feature = "alloc")]
+        if let Some(diagnostics) = &mut accounts_context.diagnostics {
+            diagnostics
+                .decoded_keys
+                .extend(out.iter().map(|account| *account.key()));
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = accounts_context;
+        Ok(Self {
+            accounts: out,
+            next_account: 0,
+            visited: StackVec::new(),
+        })
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        (0, Some(N))
+    }
+}
+/// Visited accounts are validated by [`next`](LazyRest::next).
+impl<T, const N: usize> ValidateAccounts<()> for LazyRest<T, N>
+where
+    T: Accounts,
+{
+    #[inline]
+    fn validate(&mut self, _accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
+        Ok(())
+    }
+}
+impl<T, const N: usize, A> CleanupAccounts<A> for LazyRest<T, N>
+where
+    T: CleanupAccounts<A>,
+    A: Clone,
+{
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize> SignerSeeds for LazyRest<T, N>
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self.iter() {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize, C> SupportsConstraint<C> for LazyRest<T, N>
+where
+    T: SupportsConstraint<C>,
+{
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::early_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::late_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, constraint, context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::signer::Signer;
+    use crate::error::ErrorCode;
+    use crate::test_util::{NoAccounts, TestAccount};
+
+    #[test]
+    fn visits_on_demand() {
+        let mut first = TestAccount::new([1; 32]).signer();
+        let mut second = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let mut rest: LazyRest<Signer, 2> = LazyRest::try_accounts(
+            &mut context,
+            &mut [first.info(), second.info()].into_iter(),
+            (),
+        )
+        .unwrap();
+        assert_eq!(rest.remaining(), 2);
+        assert!(rest.is_empty());
+
+        let visited = rest.next(&mut context, (), ()).unwrap().unwrap();
+        assert_eq!(visited.key(), &[1; 32]);
+        assert_eq!(rest.remaining(), 1);
+        assert_eq!(rest.len(), 1);
+
+        // Validated when visited.
+        assert_eq!(
+            rest.next(&mut context, (), ())
+                .unwrap()
+                .map_err(|error| error.error)
+                .unwrap_err(),
+            ErrorCode::AccountNotSigner.into()
+        );
+        assert_eq!(rest.remaining(), 0);
+        assert_eq!(rest.len(), 1);
+        assert!(rest.next(&mut context, (), ()).is_none());
+    }
+
+    #[test]
+    fn fails_over_capacity() {
+        let mut accounts = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            <LazyRest<AccountInfo, 2>>::try_accounts(
+                &mut context,
+                &mut accounts.iter_mut().map(TestAccount::info),
+                (),
+            )
+            .map_err(|error| error.error)
+            .unwrap_err(),
+            ErrorCode::AccountsCapacityExceeded.into()
+        );
+    }
+
+    #[test]
+    fn fails_when_visited_is_full() {
+        let mut account = TestAccount::new([1; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let mut rest: LazyRest<NoAccounts, 1> =
+            LazyRest::try_accounts(&mut context, &mut [account.info()].into_iter(), ()).unwrap();
+        assert!(rest.next(&mut context, (), ()).unwrap().is_ok());
+        assert_eq!(
+            rest.next(&mut context, (), ())
+                .unwrap()
+                .map(|_| ())
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountsCapacityExceeded.into())
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collects_failures_of_visited_accounts() {
+        let mut signer = TestAccount::new([1; 32]).signer();
+        let mut other = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let (result, diagnostics) = context.collect_all(|context| {
+            let mut rest: LazyRest<Signer, 2> = LazyRest::try_accounts(
+                context,
+                &mut [signer.info(), other.info()].into_iter(),
+                (),
+            )?;
+            while let Some(visited) = rest.next(context, (), ()) {
+                visited?;
+            }
+            Ok(rest.len())
+        });
+        assert_eq!(
+            result.map_err(|error| error.error),
+            Err(ErrorCode::AccountNotSigner.into())
+        );
+        assert_eq!(diagnostics.failures.len(), 1);
+        assert_eq!(diagnostics.failures[0].account_index, Some(1));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reports_full_visited() {
+        let mut account = TestAccount::new([1; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let (result, diagnostics) = context.collect_all(|context| {
+            let mut rest: LazyRest<NoAccounts, 1> =
+                LazyRest::try_accounts(context, &mut [account.info()].into_iter(), ())?;
+            rest.next(context, (), ()).unwrap()?;
+            rest.next(context, (), ()).unwrap().map(|_| ())
+        });
+        assert_eq!(
+            result.map_err(|error| error.error),
+            Err(ErrorCode::AccountsCapacityExceeded.into())
+        );
+        assert_eq!(diagnostics.failures.len(), 1);
+        assert_eq!(diagnostics.failures[0].account_index, Some(0));
+    }
+}
@@ -1,9 +1,19 @@
 #[cfg(feature = "borsh")]
 pub mod borsh_account;
+#[cfg(feature = "alloc")]
+pub mod bounded;
 #[cfg(feature = "bytemuck")]
 pub mod bytemuck_account;
+pub mod constrained;
+#[cfg(feature = "alloc")]
+pub mod counted;
+pub mod lazy_rest;
 pub mod mutable;
 pub mod program;
+pub mod program_data;
+#[cfg(feature = "alloc")]
 pub mod rest;
+pub mod rest_n;
 pub mod seeded;
 pub mod signer;
+pub mod upgradeable_program;
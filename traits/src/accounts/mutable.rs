@@ -1,36 +1,69 @@
-use crate::error::AnchorResult;
+use crate::error::{AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::constraint::SupportsConstraint;
-use crate::traits::maybe_bool::True;
+use crate::traits::maybe_bool::{False, True};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
-use derive_more::{Deref, DerefMut};
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::panic::Location;
+use derive_more::Deref;
+use derive_where::derive_where;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
-use pinocchio::program_error::ProgramError;
 
-pub type Mut<T = AccountInfo> = Mutability<T, true>;
-pub type ReadOnly<T = AccountInfo> = Mutability<T, false>;
+pub type Mut<T = AccountInfo, E = ()> = Mutability<T, true, E>;
+pub type ReadOnly<T = AccountInfo, E = ()> = Mutability<T, false, E>;
 
-#[derive(Copy, Clone, Debug, Deref, DerefMut)]
-pub struct Mutability<T, const IS_MUT: bool>(pub T);
+/// `E` generates the error returned when the account's writability does not match `IS_MUT`.
+///
+/// Writability is checked on decode so [`SingleAccount::Mutable`] holds for the whole lifecycle,
+/// the wrapped account is only reachable by reference or [`Mutability::into_inner`] so it can't
+/// be swapped for one that was not checked.
+#[derive_where(Clone; T: Clone)]
+#[derive_where(Copy; T: Copy)]
+#[derive_where(Debug; T: Debug)]
+#[derive(Deref)]
+pub struct Mutability<T, const IS_MUT: bool, E = ()> {
+    #[deref]
+    inner: T,
+    _error: PhantomData<fn() -> E>,
+}
+impl<T, const IS_MUT: bool, E> Mutability<T, IS_MUT, E> {
+    /// # Safety
+    /// Every account in `inner` must be writable if `IS_MUT` and read only otherwise, as
+    /// read only data is accessed without borrow checks.
+    #[inline]
+    pub unsafe fn new_unchecked(inner: T) -> Self {
+        Self {
+            inner,
+            _error: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
 
-impl<T, const IS_MUT: bool> Accounts for Mutability<T, IS_MUT>
+impl<T, const IS_MUT: bool, E> Accounts for Mutability<T, IS_MUT, E>
 where
     T: Accounts,
 {
     #[inline]
     fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
-        T::to_account_metas(&self.0, is_signer)
+        T::to_account_metas(&self.inner, is_signer)
     }
 
     #[inline]
     fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
-        T::to_account_infos(&self.0)
+        T::to_account_infos(&self.inner)
     }
 }
-unsafe impl<T> SingleAccount for Mutability<T, true>
+unsafe impl<T, E> SingleAccount for Mutability<T, true, E>
 where
     T: SingleAccount,
 {
@@ -39,32 +72,43 @@ where
 
     #[inline]
     fn account_info_ref(&self) -> &AccountInfo {
-        T::account_info_ref(&self.0)
+        T::account_info_ref(&self.inner)
     }
 }
-unsafe impl<T> SingleAccount for Mutability<T, false>
+unsafe impl<T, E> SingleAccount for Mutability<T, false, E>
 where
     T: SingleAccount,
 {
-    type Mutable = True;
+    type Mutable = False;
     type CanSign = T::CanSign;
 
     #[inline]
     fn account_info_ref(&self) -> &AccountInfo {
-        T::account_info_ref(&self.0)
+        T::account_info_ref(&self.inner)
     }
 }
-impl<T, A, const IS_MUT: bool> DecodeAccounts<A> for Mutability<T, IS_MUT>
+impl<T, A, const IS_MUT: bool, E> DecodeAccounts<A> for Mutability<T, IS_MUT, E>
 where
     T: DecodeAccounts<A>,
+    E: CustomErrorGenerator + Default,
 {
-    #[inline]
     fn try_accounts(
         accounts_context: &mut AccountsContext,
         accounts: &mut impl Iterator<Item = AccountInfo>,
         arg: A,
     ) -> AnchorResult<Self> {
-        T::try_accounts(accounts_context, accounts, arg).map(Self)
+        let location = Location::caller();
+        let account = T::try_accounts(accounts_context, accounts, arg)?;
+        let mismatched = account
+            .to_account_infos()
+            .find(|a| a.is_writable() != IS_MUT);
+        match mismatched {
+            // Safety: writability was just checked.
+            None => Ok(unsafe { Self::new_unchecked(account) }),
+            Some(mismatched) => Err(E::default()
+                .generate(ErrorCode::ConstraintMut.into())
+                .logged(location, Some(mismatched.key()))),
+        }
     }
 
     #[inline]
@@ -72,34 +116,37 @@ where
         T::size_hint()
     }
 }
-impl<T, A, const IS_MUT: bool> ValidateAccounts<A> for Mutability<T, IS_MUT>
+impl<T, A, const IS_MUT: bool, E> ValidateAccounts<A> for Mutability<T, IS_MUT, E>
 where
     T: ValidateAccounts<A>,
 {
+    #[inline]
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
-        if self.to_account_infos().all(|a| {
-            if const { IS_MUT } {
-                a.is_writable()
-            } else {
-                !a.is_writable()
-            }
-        }) {
-            T::validate(&mut self.0, accounts_context, arg)
-        } else {
-            Err(ProgramError::AccountBorrowFailed)
-        }
+        T::validate(&mut self.inner, accounts_context, arg)
     }
 }
-impl<T, A, const IS_MUT: bool> CleanupAccounts<A> for Mutability<T, IS_MUT>
+impl<T, A, const IS_MUT: bool, E> CleanupAccounts<A> for Mutability<T, IS_MUT, E>
 where
     T: CleanupAccounts<A>,
 {
     #[inline]
     fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
-        T::cleanup(&mut self.0, accounts_context, arg)
+        T::cleanup(&mut self.inner, accounts_context, arg)
+    }
+}
+impl<T, const IS_MUT: bool, E> SignerSeeds for Mutability<T, IS_MUT, E>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        T::signer_seeds(&self.inner, buffer)
     }
 }
-impl<T, C, const IS_MUT: bool> SupportsConstraint<C> for Mutability<T, IS_MUT>
+impl<T, C, const IS_MUT: bool, E> SupportsConstraint<C> for Mutability<T, IS_MUT, E>
 where
     T: SupportsConstraint<C>,
 {
@@ -108,7 +155,7 @@ where
         constraint: &mut C,
         context: &mut AccountsContext,
     ) -> AnchorResult {
-        T::early_validation(&mut self.0, constraint, context)
+        T::early_validation(&mut self.inner, constraint, context)
     }
 
     fn late_validation(
@@ -116,10 +163,10 @@ where
         constraint: &mut C,
         context: &mut AccountsContext,
     ) -> AnchorResult {
-        T::late_validation(&mut self.0, constraint, context)
+        T::late_validation(&mut self.inner, constraint, context)
     }
 
     fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
-        T::cleanup(&mut self.0, constraint, context)
+        T::cleanup(&mut self.inner, constraint, context)
     }
 }
@@ -1,38 +1,44 @@
-use crate::error::AnchorResult;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::constraint::SupportsConstraint;
 use crate::traits::program::ProgramId;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use core::fmt::Debug;
 use core::marker::PhantomData;
+use core::panic::Location;
 use derive_more::{Deref, DerefMut};
 use derive_where::derive_where;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
-use pinocchio::program_error::ProgramError;
 
+pub type ExecutableProgram<P, T = AccountInfo, E = ()> = Program<P, T, E, true>;
+
+/// Validates the key is `P::ID`, and that the account is executable if `EXECUTABLE`.
 #[derive_where(Clone; T: Clone)]
 #[derive_where(Copy; T: Copy)]
 #[derive_where(Debug; T: Debug)]
 #[derive(Deref, DerefMut)]
-pub struct Program<P: ProgramId, T = AccountInfo> {
+pub struct Program<P: ProgramId, T = AccountInfo, E = (), const EXECUTABLE: bool = false> {
     #[deref]
     #[deref_mut]
     info: T,
     _program: PhantomData<fn() -> P>,
+    _error: PhantomData<fn() -> E>,
 }
-impl<P: ProgramId, T> Program<P, T> {
+impl<P: ProgramId, T, E, const EXECUTABLE: bool> Program<P, T, E, EXECUTABLE> {
     #[inline]
     pub fn new_unchecked(info: T) -> Self {
         Self {
             info,
             _program: PhantomData,
+            _error: PhantomData,
         }
     }
 }
-impl<P: ProgramId, T> Accounts for Program<P, T>
+impl<P: ProgramId, T, E, const EXECUTABLE: bool> Accounts for Program<P, T, E, EXECUTABLE>
 where
     T: Accounts,
 {
@@ -46,7 +52,8 @@ where
         T::to_account_infos(&self.info)
     }
 }
-unsafe impl<P: ProgramId, T> SingleAccount for Program<P, T>
+unsafe impl<P: ProgramId, T, E, const EXECUTABLE: bool> SingleAccount
+    for Program<P, T, E, EXECUTABLE>
 where
     T: SingleAccount,
 {
@@ -58,7 +65,8 @@ where
         T::account_info_ref(&self.info)
     }
 }
-impl<P: ProgramId, T, A> DecodeAccounts<A> for Program<P, T>
+impl<P: ProgramId, T, A, E, const EXECUTABLE: bool> DecodeAccounts<A>
+    for Program<P, T, E, EXECUTABLE>
 where
     T: DecodeAccounts<A>,
 {
@@ -76,19 +84,35 @@ where
         T::size_hint()
     }
 }
-impl<P: ProgramId, T, A> ValidateAccounts<A> for Program<P, T>
+impl<P: ProgramId, T, A, E, const EXECUTABLE: bool> ValidateAccounts<A>
+    for Program<P, T, E, EXECUTABLE>
 where
     T: ValidateAccounts<A> + SingleAccount,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
-        if self.info.key() == &P::ID {
-            T::validate(&mut self.info, accounts_context, arg)
-        } else {
-            Err(ProgramError::IncorrectProgramId)
+        if self.info.key() != &P::ID {
+            accounts_context.report::<Self>(
+                E::default().generate(
+                    AnchorError::from(ErrorCode::InvalidProgramId)
+                        .with_pubkeys(*self.info.key(), P::ID),
+                ),
+                Location::caller(),
+                Some(self.info.key()),
+            )?;
+        }
+        if EXECUTABLE && !self.info.account_info_ref().executable() {
+            accounts_context.report::<Self>(
+                E::default().generate(ErrorCode::InvalidProgramExecutable.into()),
+                Location::caller(),
+                Some(self.info.key()),
+            )?;
         }
+        T::validate(&mut self.info, accounts_context, arg)
     }
 }
-impl<P: ProgramId, T, A> CleanupAccounts<A> for Program<P, T>
+impl<P: ProgramId, T, A, E, const EXECUTABLE: bool> CleanupAccounts<A>
+    for Program<P, T, E, EXECUTABLE>
 where
     T: CleanupAccounts<A>,
 {
@@ -97,7 +121,20 @@ where
         T::cleanup(&mut self.info, accounts_context, arg)
     }
 }
-impl<P: ProgramId, T, C> SupportsConstraint<C> for Program<P, T>
+impl<P: ProgramId, T, E, const EXECUTABLE: bool> SignerSeeds for Program<P, T, E, EXECUTABLE>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        T::signer_seeds(&self.info, buffer)
+    }
+}
+impl<P: ProgramId, T, C, E, const EXECUTABLE: bool> SupportsConstraint<C>
+    for Program<P, T, E, EXECUTABLE>
 where
     T: SupportsConstraint<C>,
 {
@@ -121,3 +158,50 @@ where
         T::cleanup(&mut self.info, constraint, context)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use crate::traits::maybe_bool::False;
+    use core::iter::once;
+    use pinocchio::pubkey::Pubkey;
+
+    struct Token;
+    impl ProgramId for Token {
+        const ID: Pubkey = [5; 32];
+
+        type IsCurrentProgram = False;
+    }
+
+    fn validate<const EXECUTABLE: bool>(account: &mut TestAccount) -> AnchorResult {
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut program = Program::<Token, AccountInfo, (), EXECUTABLE>::try_accounts(
+            &mut context,
+            &mut once(account.info()),
+            (),
+        )?;
+        program.validate(&mut context, ())
+    }
+
+    #[test]
+    fn checks_program_id() {
+        assert_eq!(validate::<false>(&mut TestAccount::new([5; 32])), Ok(()));
+        assert_eq!(
+            validate::<false>(&mut TestAccount::new([6; 32])),
+            Err(AnchorError::from(ErrorCode::InvalidProgramId).with_pubkeys([6; 32], [5; 32]))
+        );
+    }
+
+    #[test]
+    fn checks_executable() {
+        assert_eq!(
+            validate::<true>(&mut TestAccount::new([5; 32]).executable()),
+            Ok(())
+        );
+        assert_eq!(
+            validate::<true>(&mut TestAccount::new([5; 32])).map_err(|error| error.error),
+            Err(ErrorCode::InvalidProgramExecutable.into())
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::accounts::mutable::ReadOnly;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
+use crate::programs::bpf_loader_upgradeable::{
+    program_data_address, BpfLoaderUpgradeable, ProgramDataHeader,
+};
+use crate::traits::account::{
+    Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
+};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::program::{CurrentProgram, GetProgramId, ProgramId};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use crate::util::{create_program_address, find_program_address};
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::panic::Location;
+use derive_where::derive_where;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+use pinocchio::pubkey::Pubkey;
+
+/// How [`ProgramDataAccount`] gets its expected address, validated with
+/// `(ProgramDataAddress, A)`.
+#[derive(Copy, Clone, Debug)]
+pub enum ProgramDataAddress {
+    /// Read from the program account of `P`, see [`program_data_address`].
+    Program(AccountInfo),
+    /// Derived with the bump of the program data address.
+    Bump(u8),
+    /// Searched for with [`find_program_address`], which costs the most compute.
+    Find,
+}
+
+/// The program data account of `P`, parsed on decode.
+///
+/// Validates the address is the upgradeable loader's PDA of `P`'s program id.
+#[derive_where(Clone; A: Clone)]
+#[derive_where(Copy; A: Copy)]
+#[derive_where(Debug; A: Debug)]
+pub struct ProgramDataAccount<P = CurrentProgram, A = ReadOnly<AccountInfo>, E = ()> {
+    account: A,
+    header: ProgramDataHeader,
+    validated: bool,
+    _program: PhantomData<fn() -> P>,
+    _error: PhantomData<fn() -> E>,
+}
+impl<P, A, E> ProgramDataAccount<P, A, E> {
+    /// Parsed on decode, it is only known to belong to `P` after validation.
+    #[inline]
+    pub fn header(&self) -> &ProgramDataHeader {
+        &self.header
+    }
+
+    /// `None` if the program is immutable. Fails until the address is validated.
+    #[inline]
+    pub fn upgrade_authority(&self) -> AnchorResult<Option<&Pubkey>> {
+        if self.validated {
+            Ok(self.header.upgrade_authority.as_ref())
+        } else {
+            Err(ErrorCode::AccountNotProgramData.into())
+        }
+    }
+
+    #[inline]
+    pub fn last_deploy_slot(&self) -> u64 {
+        self.header.slot
+    }
+}
+impl<P, A, E> Accounts for ProgramDataAccount<P, A, E>
+where
+    A: Accounts,
+{
+    #[inline]
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        A::to_account_metas(&self.account, is_signer)
+    }
+
+    #[inline]
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        A::to_account_infos(&self.account)
+    }
+}
+unsafe impl<P, A, E> SingleAccount for ProgramDataAccount<P, A, E>
+where
+    A: SingleAccount,
+{
+    type Mutable = A::Mutable;
+    type CanSign = A::CanSign;
+
+    #[inline]
+    fn account_info_ref(&self) -> &AccountInfo {
+        A::account_info_ref(&self.account)
+    }
+}
+impl<P, A, E, Arg> DecodeAccounts<Arg> for ProgramDataAccount<P, A, E>
+where
+    A: SingleAccount + DecodeAccounts<Arg>,
+    E: CustomErrorGenerator + Default,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: Arg,
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let account = A::try_accounts(accounts_context, accounts, arg)?;
+        let header = ProgramDataHeader::parse(account.account_info_ref()).map_err(|error| {
+            E::default()
+                .generate(error)
+                .logged(location, Some(account.key()))
+        })?;
+        Ok(Self {
+            account,
+            header,
+            validated: false,
+            _program: PhantomData,
+            _error: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        A::size_hint()
+    }
+}
+impl<P, A, E, Arg> ValidateAccounts<(ProgramDataAddress, Arg)> for ProgramDataAccount<P, A, E>
+where
+    P: GetProgramId,
+    A: SingleAccount + ValidateAccounts<Arg>,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: (ProgramDataAddress, Arg),
+    ) -> AnchorResult {
+        let program_id = P::program_id(accounts_context);
+        let expected = match arg.0 {
+            ProgramDataAddress::Program(program) if program.key() != program_id => {
+                Err(AnchorError::from(ErrorCode::InvalidProgramId)
+                    .with_pubkeys(*program.key(), *program_id))
+            }
+            ProgramDataAddress::Program(program) => program_data_address(&program),
+            ProgramDataAddress::Bump(bump) => {
+                create_program_address(&[program_id, &[bump]], &BpfLoaderUpgradeable::ID)
+                    .map_err(AnchorError::from)
+            }
+            ProgramDataAddress::Find => {
+                Ok(find_program_address(&[program_id], &BpfLoaderUpgradeable::ID).0)
+            }
+        };
+        let error = match expected {
+            Ok(expected) if self.key() == &expected => None,
+            Ok(expected) => Some(
+                AnchorError::from(ErrorCode::AccountNotProgramData)
+                    .with_pubkeys(*self.key(), expected),
+            ),
+            Err(error) => Some(error),
+        };
+        match error {
+            None => self.validated = true,
+            Some(error) => accounts_context.report::<Self>(
+                E::default().generate(error),
+                Location::caller(),
+                Some(self.key()),
+            )?,
+        }
+        A::validate(&mut self.account, accounts_context, arg.1)
+    }
+}
+impl<P, A, E, Arg> CleanupAccounts<Arg> for ProgramDataAccount<P, A, E>
+where
+    A: CleanupAccounts<Arg>,
+{
+    #[inline]
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: Arg) -> AnchorResult {
+        A::cleanup(&mut self.account, accounts_context, arg)
+    }
+}
+impl<P, A, E> SignerSeeds for ProgramDataAccount<P, A, E>
+where
+    A: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        A::signer_seeds(&self.account, buffer)
+    }
+}
+impl<P, A, E, C> SupportsConstraint<C> for ProgramDataAccount<P, A, E>
+where
+    A: SupportsConstraint<C>,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        A::early_validation(&mut self.account, constraint, context)
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        A::late_validation(&mut self.account, constraint, context)
+    }
+
+    #[inline]
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        A::cleanup(&mut self.account, constraint, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::upgrade_authority::UpgradeAuthority;
+    use crate::test_util::TestAccount;
+    use core::iter::once;
+
+    const PROGRAM_ID: Pubkey = [5; 32];
+    const AUTHORITY: Pubkey = [7; 32];
+
+    fn program(program_data: Pubkey) -> TestAccount {
+        let mut data = [0; 36];
+        data[..4].copy_from_slice(&2u32.to_le_bytes());
+        data[4..].copy_from_slice(&program_data);
+        TestAccount::new(PROGRAM_ID)
+            .owner(BpfLoaderUpgradeable::ID)
+            .executable()
+            .data(&data)
+    }
+
+    fn program_data(key: Pubkey) -> TestAccount {
+        let mut data = [0; ProgramDataHeader::LEN];
+        data[..4].copy_from_slice(&3u32.to_le_bytes());
+        data[4..12].copy_from_slice(&9u64.to_le_bytes());
+        data[12] = 1;
+        data[13..].copy_from_slice(&AUTHORITY);
+        TestAccount::new(key)
+            .owner(BpfLoaderUpgradeable::ID)
+            .data(&data)
+    }
+
+    fn decode(account: &mut TestAccount, context: &mut AccountsContext) -> ProgramDataAccount {
+        ProgramDataAccount::try_accounts(context, &mut once(account.info()), ()).unwrap()
+    }
+
+    #[test]
+    fn address_from_program_account() {
+        let mut program = program([6; 32]);
+        let mut account = program_data([6; 32]);
+        let mut context = AccountsContext::new(&PROGRAM_ID);
+        let mut program_data = decode(&mut account, &mut context);
+        assert_eq!(program_data.last_deploy_slot(), 9);
+        assert!(program_data.upgrade_authority().is_err());
+        assert!(UpgradeAuthority::new(&program_data).is_err());
+
+        let arg = (ProgramDataAddress::Program(program.info()), ());
+        assert_eq!(program_data.validate(&mut context, arg), Ok(()));
+        assert_eq!(program_data.upgrade_authority(), Ok(Some(&AUTHORITY)));
+        assert_eq!(
+            UpgradeAuthority::new(&program_data).map(|c| c.upgrade_authority),
+            Ok(Some(AUTHORITY))
+        );
+    }
+
+    #[test]
+    fn wrong_address_is_not_validated() {
+        let mut program = program([6; 32]);
+        let mut other_program = TestAccount::new([8; 32]);
+        let mut account = program_data([4; 32]);
+        let mut context = AccountsContext::new(&PROGRAM_ID);
+        let mut program_data = decode(&mut account, &mut context);
+
+        let error = program_data
+            .validate(
+                &mut context,
+                (ProgramDataAddress::Program(program.info()), ()),
+            )
+            .unwrap_err();
+        assert_eq!(error.error, ErrorCode::AccountNotProgramData.into());
+        assert_eq!(error.compared_pubkeys, Some(([4; 32], [6; 32])));
+
+        let error = program_data
+            .validate(
+                &mut context,
+                (ProgramDataAddress::Program(other_program.info()), ()),
+            )
+            .unwrap_err();
+        assert_eq!(error.error, ErrorCode::InvalidProgramId.into());
+        assert!(program_data.upgrade_authority().is_err());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn address_from_bump() {
+        let (key, bump) = find_program_address(&[&PROGRAM_ID], &BpfLoaderUpgradeable::ID);
+        let mut account = program_data(key);
+        let mut context = AccountsContext::new(&PROGRAM_ID);
+
+        let mut program_data = decode(&mut account, &mut context);
+        let arg = (ProgramDataAddress::Bump(bump.wrapping_sub(1)), ());
+        assert!(program_data.validate(&mut context, arg).is_err());
+        let arg = (ProgramDataAddress::Bump(bump), ());
+        assert_eq!(program_data.validate(&mut context, arg), Ok(()));
+
+        let mut program_data = decode(&mut account, &mut context);
+        let arg = (ProgramDataAddress::Find, ());
+        assert_eq!(program_data.validate(&mut context, arg), Ok(()));
+        assert_eq!(program_data.upgrade_authority(), Ok(Some(&AUTHORITY)));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::AnchorResult;
 use crate::traits::account::{Accounts, CleanupAccounts, DecodeAccounts, ValidateAccounts};
 use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use alloc::vec::Vec;
 use derive_more::{Deref, DerefMut};
@@ -70,6 +71,20 @@ where
         Ok(())
     }
 }
+impl<T> SignerSeeds for Rest<T>
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self.iter() {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
 impl<T, C> SupportsConstraint<C> for Rest<T>
 where
     T: SupportsConstraint<C>,
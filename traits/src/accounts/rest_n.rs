@@ -0,0 +1,201 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, CleanupAccounts, DecodeAccounts, ValidateAccounts};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use crate::util::StackVec;
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+use derive_where::derive_where;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+
+/// Like [`Rest`](crate::accounts::rest::Rest) but stored inline with room for `N` items. Fails if
+/// more accounts remain.
+#[derive_where(Clone; T: Clone)]
+#[derive_where(Debug; T: Debug)]
+pub struct RestN<T, const N: usize>(StackVec<T, N>);
+impl<T, const N: usize> Deref for RestN<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T, const N: usize> DerefMut for RestN<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T, const N: usize> Accounts for RestN<T, N>
+where
+    T: Accounts,
+{
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        self.iter().flat_map(T::to_account_infos)
+    }
+
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        self.iter()
+            .flat_map(move |a| T::to_account_metas(a, is_signer))
+    }
+}
+impl<T, const N: usize, A> DecodeAccounts<A> for RestN<T, N>
+where
+    T: DecodeAccounts<A>,
+    A: Clone,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: A,
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let mut accounts = accounts.peekable();
+
+        let mut out = StackVec::new();
+        while let Some(account) = accounts.peek() {
+            if out.len() == N {
+                return Err(AnchorError::from(ErrorCode::AccountsCapacityExceeded)
+                    .logged(location, Some(account.key())));
+            }
+            let t = T::try_accounts(accounts_context, &mut accounts, arg.clone())?;
+            // Can't be full, checked above.
+            let _ = out.push(t);
+        }
+
+        Ok(Self(out))
+    }
+
+    fn size_hint() -> (usize, Option<usize>) {
+        (0, T::size_hint().1.and_then(|v| v.checked_mul(N)))
+    }
+}
+impl<T, const N: usize, A> ValidateAccounts<A> for RestN<T, N>
+where
+    T: ValidateAccounts<A>,
+    A: Clone,
+{
+    fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::validate(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize, A> CleanupAccounts<A> for RestN<T, N>
+where
+    T: CleanupAccounts<A>,
+    A: Clone,
+{
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, accounts_context, arg.clone())?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize> SignerSeeds for RestN<T, N>
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self.iter() {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize, C> SupportsConstraint<C> for RestN<T, N>
+where
+    T: SupportsConstraint<C>,
+{
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::early_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::late_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, constraint, context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_util::{NoAccounts, TestAccount};
+
+    #[test]
+    fn decodes_up_to_n() {
+        let mut accounts = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let rest: RestN<AccountInfo, 3> = RestN::try_accounts(
+            &mut context,
+            &mut accounts.iter_mut().map(TestAccount::info),
+            (),
+        )
+        .unwrap();
+        assert_eq!(rest.len(), 3);
+        assert_eq!(rest[2].key(), &[3; 32]);
+
+        let rest: RestN<AccountInfo, 3> =
+            RestN::try_accounts(&mut context, &mut [].into_iter(), ()).unwrap();
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn fails_over_capacity() {
+        let mut accounts = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            <RestN<AccountInfo, 2>>::try_accounts(
+                &mut context,
+                &mut accounts.iter_mut().map(TestAccount::info),
+                (),
+            )
+            .map_err(|error| error.error)
+            .unwrap_err(),
+            ErrorCode::AccountsCapacityExceeded.into()
+        );
+    }
+
+    #[test]
+    fn size_hint_saturates() {
+        assert_eq!(
+            <RestN<AccountInfo, 3> as DecodeAccounts<()>>::size_hint(),
+            (0, Some(3))
+        );
+        assert_eq!(
+            <RestN<NoAccounts<{ usize::MAX }>, 2> as DecodeAccounts<()>>::size_hint(),
+            (0, None)
+        );
+    }
+}
@@ -1,18 +1,22 @@
-use crate::error::AnchorResult;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::constraint::SupportsConstraint;
-use crate::traits::maybe_bool::{MaybeBool, Or};
+use crate::traits::maybe_bool::{Bool, MaybeBool, Or};
 use crate::traits::program::{CurrentProgram, GetProgramId};
-use crate::traits::seeds::{SeededAccount, Seeds};
+use crate::traits::seeds::{DataSeeds, SeededAccount, Seeds, StoredBump};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
+use crate::util::create_program_address;
+use core::iter::once;
 use core::marker::PhantomData;
 use core::ops::BitOr;
+use core::panic::Location;
+use core::slice;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
-use pinocchio::program_error::ProgramError;
-use pinocchio::pubkey::{create_program_address, Pubkey};
+use pinocchio::pubkey::Pubkey;
 
 #[derive(Copy, Clone, Debug)]
 pub struct SeedsWithBump<S> {
@@ -26,18 +30,49 @@ impl<S> SeedsWithBump<S> {
     {
         self.seeds
             .with_seeds_and_bump(self.bump, |seeds| create_program_address(seeds, program_id))
+            .map_err(AnchorError::from)
     }
 }
 
+/// Validates seeds with the bump from the account's data, see [`StoredBump`].
 #[derive(Copy, Clone, Debug)]
-pub struct Seeded<T, S = <T as SeededAccount>::Seeds, P = CurrentProgram> {
+pub struct WithStoredBump<S>(pub S);
+
+/// Validates seeds with a supplied bump, rejecting it unless it is the canonical bump. Costs a
+/// [`find_program_address`](pinocchio::pubkey::find_program_address).
+#[derive(Copy, Clone, Debug)]
+pub struct Canonical<S>(pub SeedsWithBump<S>);
+
+/// Validates seeds computed from the inner account's data, see [`DataSeeds`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SeedsFromData;
+
+/// Validates seeds computed by `F` from the inner account and the context. `F` may capture other
+/// decoded accounts.
+#[derive(Copy, Clone, Debug)]
+pub struct SeedsFromAccounts<F>(pub F);
+
+#[derive(Copy, Clone, Debug)]
+pub struct Seeded<T, S = <T as SeededAccount>::Seeds, P = CurrentProgram, E = ()> {
     pub account: T,
     pub seeds: Option<SeedsWithBump<S>>,
     pub _phantom_program: PhantomData<fn() -> P>,
+    pub _phantom_error: PhantomData<fn() -> E>,
+}
+impl<T, S, P, E> Seeded<T, S, P, E>
+where
+    P: GetProgramId,
+{
+    /// Whether the current program can sign for this account with the validated seeds.
+    #[inline]
+    pub fn can_sign_with_seeds(&self) -> bool {
+        P::IsCurrentProgram::VALUE && self.seeds.is_some()
+    }
 }
-impl<T, S, P> Accounts for Seeded<T, S, P>
+impl<T, S, P, E> Accounts for Seeded<T, S, P, E>
 where
     T: Accounts,
+    P: GetProgramId,
 {
     #[inline]
     fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
@@ -49,7 +84,7 @@ where
         T::to_account_infos(&self.account)
     }
 }
-unsafe impl<T, S, P> SingleAccount for Seeded<T, S, P>
+unsafe impl<T, S, P, E> SingleAccount for Seeded<T, S, P, E>
 where
     T: SingleAccount,
     P: GetProgramId,
@@ -64,9 +99,10 @@ where
         T::account_info_ref(&self.account)
     }
 }
-impl<T, S, P, A> DecodeAccounts<A> for Seeded<T, S, P>
+impl<T, S, P, A, E> DecodeAccounts<A> for Seeded<T, S, P, E>
 where
     T: DecodeAccounts<A>,
+    P: GetProgramId,
 {
     fn try_accounts(
         accounts_context: &mut AccountsContext,
@@ -77,6 +113,7 @@ where
             account: T::try_accounts(accounts_context, accounts, arg)?,
             seeds: None,
             _phantom_program: PhantomData,
+            _phantom_error: PhantomData,
         })
     }
 
@@ -85,25 +122,27 @@ where
         T::size_hint()
     }
 }
-impl<T, S, P> ValidateAccounts<S> for Seeded<T, S, P>
+impl<T, S, P, E> ValidateAccounts<S> for Seeded<T, S, P, E>
 where
     T: SingleAccount + ValidateAccounts<()>,
     S: Seeds,
     P: GetProgramId,
     P::IsCurrentProgram: BitOr<T::CanSign>,
     Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: S) -> AnchorResult {
         Self::validate(self, accounts_context, (arg, ()))
     }
 }
-impl<T, S, P> ValidateAccounts<SeedsWithBump<S>> for Seeded<T, S, P>
+impl<T, S, P, E> ValidateAccounts<SeedsWithBump<S>> for Seeded<T, S, P, E>
 where
     T: SingleAccount + ValidateAccounts<()>,
     S: Seeds,
     P: GetProgramId,
     P::IsCurrentProgram: BitOr<T::CanSign>,
     Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(
         &mut self,
@@ -113,77 +152,321 @@ where
         Self::validate(self, accounts_context, (arg, ()))
     }
 }
-impl<T, S, P, A> ValidateAccounts<(S, A)> for Seeded<T, S, P>
+impl<T, S, P, A, E> ValidateAccounts<(S, A)> for Seeded<T, S, P, E>
 where
     T: SingleAccount + ValidateAccounts<A>,
     S: Seeds,
     P: GetProgramId,
     P::IsCurrentProgram: BitOr<T::CanSign>,
     Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: (S, A)) -> AnchorResult {
-        let (found_key, bump) = arg.0.find_program_address(P::program_id(accounts_context));
-
-        if found_key == *self.key() {
-            self.seeds = Some(SeedsWithBump { seeds: arg.0, bump });
-
-            T::validate(&mut self.account, accounts_context, arg.1)
-        } else {
-            Err(ProgramError::InvalidSeeds)
+        let error = match arg
+            .0
+            .try_find_program_address(P::program_id(accounts_context))
+        {
+            Ok((found_key, bump)) if found_key == *self.key() => {
+                self.seeds = Some(SeedsWithBump { seeds: arg.0, bump });
+                None
+            }
+            Ok((found_key, _)) => Some(
+                AnchorError::from(ErrorCode::ConstraintSeeds).with_pubkeys(*self.key(), found_key),
+            ),
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            accounts_context.report::<Self>(
+                E::default().generate(error),
+                Location::caller(),
+                Some(self.key()),
+            )?;
         }
+        T::validate(&mut self.account, accounts_context, arg.1)
     }
 }
-impl<T, S, P, A> ValidateAccounts<(SeedsWithBump<S>, A)> for Seeded<T, S, P>
+impl<T, S, P, A, E> ValidateAccounts<(SeedsWithBump<S>, A)> for Seeded<T, S, P, E>
 where
     T: SingleAccount + ValidateAccounts<A>,
     S: Seeds,
     P: GetProgramId,
     P::IsCurrentProgram: BitOr<T::CanSign>,
     Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(
         &mut self,
         accounts_context: &mut AccountsContext,
         arg: (SeedsWithBump<S>, A),
     ) -> AnchorResult {
-        if self.key()
-            == &arg
-                .0
-                .create_program_address(P::program_id(accounts_context))?
+        let error = match arg
+            .0
+            .create_program_address(P::program_id(accounts_context))
         {
-            self.seeds = Some(arg.0);
-
-            T::validate(&mut self.account, accounts_context, arg.1)
-        } else {
-            Err(ProgramError::InvalidSeeds)
+            Ok(expected_key) if self.key() == &expected_key => None,
+            Ok(expected_key) => Some(
+                AnchorError::from(ErrorCode::ConstraintSeeds)
+                    .with_pubkeys(*self.key(), expected_key),
+            ),
+            Err(_) => Some(ErrorCode::ConstraintSeeds.into()),
+        };
+        match error {
+            None => self.seeds = Some(arg.0),
+            Some(error) => accounts_context.report::<Self>(
+                E::default().generate(error),
+                Location::caller(),
+                Some(self.key()),
+            )?,
+        }
+        T::validate(&mut self.account, accounts_context, arg.1)
+    }
+}
+impl<T, S, P, E> ValidateAccounts<WithStoredBump<S>> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<()> + StoredBump,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: WithStoredBump<S>,
+    ) -> AnchorResult {
+        Self::validate(self, accounts_context, (arg, ()))
+    }
+}
+impl<T, S, P, A, E> ValidateAccounts<(WithStoredBump<S>, A)> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<A> + StoredBump,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: (WithStoredBump<S>, A),
+    ) -> AnchorResult {
+        let (WithStoredBump(seeds), arg) = arg;
+        match self.account.stored_bump() {
+            Ok(bump) => {
+                Self::validate(self, accounts_context, (SeedsWithBump { seeds, bump }, arg))
+            }
+            Err(error) => {
+                accounts_context.report::<Self>(
+                    E::default().generate(error),
+                    Location::caller(),
+                    Some(self.key()),
+                )?;
+                T::validate(&mut self.account, accounts_context, arg)
+            }
         }
     }
 }
-impl<T, S, P> ValidateAccounts<()> for Seeded<T, S, P>
+impl<T, S, P, E> ValidateAccounts<Canonical<S>> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<()>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: Canonical<S>,
+    ) -> AnchorResult {
+        Self::validate(self, accounts_context, (arg, ()))
+    }
+}
+impl<T, S, P, A, E> ValidateAccounts<(Canonical<S>, A)> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<A>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: (Canonical<S>, A),
+    ) -> AnchorResult {
+        let Canonical(seeds) = arg.0;
+        let error = match seeds
+            .seeds
+            .try_find_program_address(P::program_id(accounts_context))
+        {
+            Ok((found_key, bump)) if found_key == *self.key() && bump == seeds.bump => {
+                self.seeds = Some(seeds);
+                None
+            }
+            Ok((found_key, _)) => Some(
+                AnchorError::from(ErrorCode::ConstraintSeeds).with_pubkeys(*self.key(), found_key),
+            ),
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            accounts_context.report::<Self>(
+                E::default().generate(error),
+                Location::caller(),
+                Some(self.key()),
+            )?;
+        }
+        T::validate(&mut self.account, accounts_context, arg.1)
+    }
+}
+impl<T, S, P, E> ValidateAccounts<SeedsFromData> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<()> + DataSeeds<Seeds = S>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    #[inline]
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: SeedsFromData,
+    ) -> AnchorResult {
+        Self::validate(self, accounts_context, (arg, ()))
+    }
+}
+impl<T, S, P, A, E> ValidateAccounts<(SeedsFromData, A)> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<A> + DataSeeds<Seeds = S>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: (SeedsFromData, A),
+    ) -> AnchorResult {
+        match self.account.data_seeds(accounts_context) {
+            Ok(seeds) => Self::validate(self, accounts_context, (seeds, arg.1)),
+            Err(error) => {
+                accounts_context.report::<Self>(
+                    E::default().generate(error),
+                    Location::caller(),
+                    Some(self.key()),
+                )?;
+                T::validate(&mut self.account, accounts_context, arg.1)
+            }
+        }
+    }
+}
+impl<T, S, P, F, E> ValidateAccounts<SeedsFromAccounts<F>> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<()>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    F: FnOnce(&T, &AccountsContext) -> AnchorResult<S>,
+    E: CustomErrorGenerator + Default,
+{
+    #[inline]
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: SeedsFromAccounts<F>,
+    ) -> AnchorResult {
+        Self::validate(self, accounts_context, (arg, ()))
+    }
+}
+impl<T, S, P, F, A, E> ValidateAccounts<(SeedsFromAccounts<F>, A)> for Seeded<T, S, P, E>
+where
+    T: SingleAccount + ValidateAccounts<A>,
+    S: Seeds,
+    P: GetProgramId,
+    P::IsCurrentProgram: BitOr<T::CanSign>,
+    Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    F: FnOnce(&T, &AccountsContext) -> AnchorResult<S>,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(
+        &mut self,
+        accounts_context: &mut AccountsContext,
+        arg: (SeedsFromAccounts<F>, A),
+    ) -> AnchorResult {
+        let (SeedsFromAccounts(f), arg) = arg;
+        match f(&self.account, accounts_context) {
+            Ok(seeds) => Self::validate(self, accounts_context, (seeds, arg)),
+            Err(error) => {
+                accounts_context.report::<Self>(
+                    E::default().generate(error),
+                    Location::caller(),
+                    Some(self.key()),
+                )?;
+                T::validate(&mut self.account, accounts_context, arg)
+            }
+        }
+    }
+}
+impl<T, S, P, E> ValidateAccounts<()> for Seeded<T, S, P, E>
 where
     T: SingleAccount + ValidateAccounts<()>,
     S: Seeds + Default,
     P: GetProgramId,
     P::IsCurrentProgram: BitOr<T::CanSign>,
     Or<P::IsCurrentProgram, T::CanSign>: MaybeBool,
+    E: CustomErrorGenerator + Default,
 {
     #[inline]
     fn validate(&mut self, accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
         Self::validate(self, accounts_context, S::default())
     }
 }
-impl<T, S, P> CleanupAccounts<()> for Seeded<T, S, P>
+impl<T, S, P, E> CleanupAccounts<()> for Seeded<T, S, P, E>
 where
     T: CleanupAccounts<()>,
+    P: GetProgramId,
 {
     #[inline]
     fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: ()) -> AnchorResult {
         T::cleanup(&mut self.account, accounts_context, arg)
     }
 }
-impl<T, S, P, C> SupportsConstraint<C> for Seeded<T, S, P>
+impl<T, S, P, E> SignerSeeds for Seeded<T, S, P, E>
+where
+    T: SingleAccount + SignerSeeds,
+    S: Seeds,
+    P: GetProgramId,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        // Only addresses derived from the current program can be signed for.
+        if let Some(seeds) = self.seeds.as_ref().filter(|_| P::IsCurrentProgram::VALUE) {
+            buffer.push_signer(
+                *self.account.key(),
+                seeds
+                    .seeds
+                    .seeds()
+                    .chain(once(slice::from_ref(&seeds.bump))),
+            )?;
+        }
+        T::signer_seeds(&self.account, buffer)
+    }
+}
+impl<T, S, P, C, E> SupportsConstraint<C> for Seeded<T, S, P, E>
 where
     T: SupportsConstraint<C>,
+    P: GetProgramId,
 {
     #[inline]
     fn early_validation(
@@ -208,3 +491,219 @@ where
         T::cleanup(&mut self.account, constraint, context)
     }
 }
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+    use crate::client::pda::find_program_address;
+    use crate::test_util::TestAccount;
+    use crate::traits::seeds::{Const, ConstSeed};
+
+    const PROGRAM: Pubkey = [9; 32];
+    const AUTHORITY: Pubkey = [2; 32];
+
+    struct VaultSeed;
+    impl ConstSeed for VaultSeed {
+        const SEED: &'static [u8] = b"vault";
+    }
+    type VaultSeeds = (Const<VaultSeed>, Pubkey);
+
+    /// An account storing its bump in its first byte, followed by its authority.
+    struct Vault(AccountInfo);
+    impl Accounts for Vault {
+        fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+            once(self.0)
+        }
+
+        fn to_account_metas(
+            &self,
+            is_signer: Option<bool>,
+        ) -> impl Iterator<Item = AccountMeta<'_>> {
+            self.0.to_account_metas(is_signer)
+        }
+    }
+    unsafe impl SingleAccount for Vault {
+        type Mutable = <AccountInfo as SingleAccount>::Mutable;
+        type CanSign = <AccountInfo as SingleAccount>::CanSign;
+
+        fn account_info_ref(&self) -> &AccountInfo {
+            &self.0
+        }
+    }
+    impl DecodeAccounts<()> for Vault {
+        fn try_accounts(
+            accounts_context: &mut AccountsContext,
+            accounts: &mut impl Iterator<Item = AccountInfo>,
+            arg: (),
+        ) -> AnchorResult<Self> {
+            AccountInfo::try_accounts(accounts_context, accounts, arg).map(Self)
+        }
+    }
+    impl ValidateAccounts<()> for Vault {
+        fn validate(&mut self, _accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
+            Ok(())
+        }
+    }
+    impl StoredBump for Vault {
+        fn stored_bump(&self) -> AnchorResult<u8> {
+            self.0
+                .try_borrow_data()?
+                .first()
+                .copied()
+                .ok_or_else(|| ErrorCode::AccountDidNotDeserialize.into())
+        }
+    }
+    impl DataSeeds for Vault {
+        type Seeds = VaultSeeds;
+
+        fn data_seeds(&self, _context: &AccountsContext) -> AnchorResult<Self::Seeds> {
+            let data = self.0.try_borrow_data()?;
+            let authority = data
+                .get(1..)
+                .and_then(|data| data.first_chunk())
+                .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+            Ok((Const::default(), *authority))
+        }
+    }
+
+    fn vault_seeds() -> VaultSeeds {
+        (Const::default(), AUTHORITY)
+    }
+
+    /// The address and canonical bump of the vault of [`AUTHORITY`].
+    fn vault_address() -> (Pubkey, u8) {
+        find_program_address(&[b"vault", &AUTHORITY], &PROGRAM)
+    }
+
+    /// A valid bump of the vault of [`AUTHORITY`] below the canonical one.
+    fn non_canonical_bump() -> (Pubkey, u8) {
+        let (_, canonical) = vault_address();
+        (0..canonical)
+            .rev()
+            .find_map(|bump| {
+                vault_seeds()
+                    .with_seeds_and_bump(bump, |seeds| {
+                        crate::client::pda::create_program_address(seeds, &PROGRAM)
+                    })
+                    .ok()
+                    .map(|address| (address, bump))
+            })
+            .unwrap()
+    }
+
+    fn validate<V>(account: &mut TestAccount, arg: V) -> AnchorResult<bool>
+    where
+        Seeded<Vault, VaultSeeds>: ValidateAccounts<V>,
+    {
+        let mut context = AccountsContext::new(&PROGRAM);
+        let mut vault =
+            Seeded::<Vault, VaultSeeds>::try_accounts(&mut context, &mut once(account.info()), ())?;
+        vault.validate(&mut context, arg)?;
+        Ok(vault.can_sign_with_seeds())
+    }
+
+    #[test]
+    fn with_stored_bump() {
+        let (address, bump) = vault_address();
+        let mut vault = TestAccount::new(address).data(&[bump]);
+        assert_eq!(
+            validate(&mut vault, WithStoredBump(vault_seeds())),
+            Ok(true)
+        );
+
+        let mut wrong_bump = TestAccount::new(address).data(&[bump.wrapping_sub(1)]);
+        assert_eq!(
+            validate(&mut wrong_bump, WithStoredBump(vault_seeds())).map_err(|error| error.error),
+            Err(ErrorCode::ConstraintSeeds.into())
+        );
+    }
+
+    #[test]
+    fn with_stored_bump_reports_missing_bump() {
+        let (address, _) = vault_address();
+        let mut empty = TestAccount::new(address);
+        assert_eq!(
+            validate(&mut empty, WithStoredBump(vault_seeds())),
+            Err(ErrorCode::AccountDidNotDeserialize.into())
+        );
+
+        let mut context = AccountsContext::new(&PROGRAM);
+        let (result, diagnostics) = context.collect_all(|context| {
+            let mut vault =
+                Seeded::<Vault, VaultSeeds>::try_accounts(context, &mut once(empty.info()), ())?;
+            vault.validate(context, WithStoredBump(vault_seeds()))?;
+            Ok(vault.can_sign_with_seeds())
+        });
+        assert_eq!(result, Err(ErrorCode::AccountDidNotDeserialize.into()));
+        assert_eq!(diagnostics.failures.len(), 1);
+    }
+
+    #[test]
+    fn canonical() {
+        let (address, bump) = vault_address();
+        let mut vault = TestAccount::new(address);
+        let seeds = SeedsWithBump {
+            seeds: vault_seeds(),
+            bump,
+        };
+        assert_eq!(validate(&mut vault, Canonical(seeds)), Ok(true));
+
+        let (address, bump) = non_canonical_bump();
+        let mut non_canonical = TestAccount::new(address);
+        let seeds = SeedsWithBump {
+            seeds: vault_seeds(),
+            bump,
+        };
+        assert_eq!(validate(&mut non_canonical, seeds), Ok(true));
+        assert_eq!(
+            validate(&mut non_canonical, Canonical(seeds)).map_err(|error| error.error),
+            Err(ErrorCode::ConstraintSeeds.into())
+        );
+    }
+
+    #[test]
+    fn seeds_from_data() {
+        let (address, bump) = vault_address();
+        let mut data = [bump; 33];
+        data[1..].copy_from_slice(&AUTHORITY);
+        let mut vault = TestAccount::new(address).data(&data);
+        assert_eq!(validate(&mut vault, SeedsFromData), Ok(true));
+
+        data[1..].copy_from_slice(&[3; 32]);
+        let mut other_authority = TestAccount::new(address).data(&data);
+        assert_eq!(
+            validate(&mut other_authority, SeedsFromData).map_err(|error| error.error),
+            Err(ErrorCode::ConstraintSeeds.into())
+        );
+
+        let mut empty = TestAccount::new(address);
+        assert_eq!(
+            validate(&mut empty, SeedsFromData),
+            Err(ErrorCode::AccountDidNotDeserialize.into())
+        );
+    }
+
+    #[test]
+    fn seeds_from_accounts() {
+        let (address, _) = vault_address();
+        let mut authority = TestAccount::new(AUTHORITY);
+        let authority = authority.info();
+        let seeds = |_: &Vault, _: &AccountsContext| Ok((Const::default(), *authority.key()));
+        let mut vault = TestAccount::new(address);
+        assert_eq!(validate(&mut vault, SeedsFromAccounts(seeds)), Ok(true));
+
+        let mut other = TestAccount::new([4; 32]);
+        assert_eq!(
+            validate(&mut other, SeedsFromAccounts(seeds)).map_err(|error| error.error),
+            Err(ErrorCode::ConstraintSeeds.into())
+        );
+
+        let failing = |_: &Vault, _: &AccountsContext| -> AnchorResult<VaultSeeds> {
+            Err(ErrorCode::ConstraintRaw.into())
+        };
+        assert_eq!(
+            validate(&mut vault, SeedsFromAccounts(failing)),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+    }
+}
@@ -1,32 +1,56 @@
-use crate::error::AnchorResult;
+use crate::error::{AnchorResult, CustomErrorGenerator, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::constraint::SupportsConstraint;
 use crate::traits::maybe_bool::True;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::panic::Location;
 use derive_more::{Deref, DerefMut};
+use derive_where::derive_where;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
-use pinocchio::program_error::ProgramError;
 
-#[derive(Copy, Clone, Debug, Deref, DerefMut)]
-pub struct Signer<T = AccountInfo>(pub T);
-impl<T> Accounts for Signer<T>
+/// `E` generates the error returned when the account did not sign.
+///
+/// Built with [`Signer::new`], the wrapped account is [`Signer::inner`].
+#[derive_where(Clone; T: Clone)]
+#[derive_where(Copy; T: Copy)]
+#[derive_where(Debug; T: Debug)]
+#[derive(Deref, DerefMut)]
+pub struct Signer<T = AccountInfo, E = ()> {
+    #[deref]
+    #[deref_mut]
+    pub inner: T,
+    _error: PhantomData<fn() -> E>,
+}
+impl<T, E> Signer<T, E> {
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            _error: PhantomData,
+        }
+    }
+}
+impl<T, E> Accounts for Signer<T, E>
 where
     T: Accounts,
 {
     #[inline]
     fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
-        T::to_account_metas(&self.0, is_signer)
+        T::to_account_metas(&self.inner, is_signer)
     }
 
     #[inline]
     fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
-        T::to_account_infos(&self.0)
+        T::to_account_infos(&self.inner)
     }
 }
-unsafe impl<T> SingleAccount for Signer<T>
+unsafe impl<T, E> SingleAccount for Signer<T, E>
 where
     T: SingleAccount,
 {
@@ -35,10 +59,10 @@ where
 
     #[inline]
     fn account_info_ref(&self) -> &AccountInfo {
-        T::account_info_ref(&self.0)
+        T::account_info_ref(&self.inner)
     }
 }
-impl<T, A> DecodeAccounts<A> for Signer<T>
+impl<T, A, E> DecodeAccounts<A> for Signer<T, E>
 where
     T: DecodeAccounts<A>,
 {
@@ -48,7 +72,7 @@ where
         accounts: &mut impl Iterator<Item = AccountInfo>,
         arg: A,
     ) -> AnchorResult<Self> {
-        T::try_accounts(accounts_context, accounts, arg).map(Self)
+        T::try_accounts(accounts_context, accounts, arg).map(Self::new)
     }
 
     #[inline]
@@ -56,28 +80,45 @@ where
         T::size_hint()
     }
 }
-impl<T, A> ValidateAccounts<A> for Signer<T>
+impl<T, A, E> ValidateAccounts<A> for Signer<T, E>
 where
     T: ValidateAccounts<A>,
+    E: CustomErrorGenerator + Default,
 {
     fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
-        if self.to_account_infos().all(|a| a.is_signer()) {
-            T::validate(&mut self.0, accounts_context, arg)
-        } else {
-            Err(ProgramError::MissingRequiredSignature)
+        let not_signer = self.to_account_infos().find(|a| !a.is_signer());
+        if let Some(account) = not_signer {
+            accounts_context.report::<Self>(
+                E::default().generate(ErrorCode::AccountNotSigner.into()),
+                Location::caller(),
+                Some(account.key()),
+            )?;
         }
+        T::validate(&mut self.inner, accounts_context, arg)
     }
 }
-impl<T, A> CleanupAccounts<A> for Signer<T>
+impl<T, A, E> CleanupAccounts<A> for Signer<T, E>
 where
     T: CleanupAccounts<A>,
 {
     #[inline]
     fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
-        T::cleanup(&mut self.0, accounts_context, arg)
+        T::cleanup(&mut self.inner, accounts_context, arg)
     }
 }
-impl<T, A> SupportsConstraint<A> for Signer<T>
+impl<T, E> SignerSeeds for Signer<T, E>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        T::signer_seeds(&self.inner, buffer)
+    }
+}
+impl<T, A, E> SupportsConstraint<A> for Signer<T, E>
 where
     T: SupportsConstraint<A>,
 {
@@ -87,7 +128,7 @@ where
         constraint: &mut A,
         context: &mut AccountsContext,
     ) -> AnchorResult {
-        T::early_validation(&mut self.0, constraint, context)
+        T::early_validation(&mut self.inner, constraint, context)
     }
 
     #[inline]
@@ -96,11 +137,39 @@ where
         constraint: &mut A,
         context: &mut AccountsContext,
     ) -> AnchorResult {
-        T::late_validation(&mut self.0, constraint, context)
+        T::late_validation(&mut self.inner, constraint, context)
     }
 
     #[inline]
     fn cleanup(&mut self, constraint: &mut A, context: &mut AccountsContext) -> AnchorResult {
-        T::cleanup(&mut self.0, constraint, context)
+        T::cleanup(&mut self.inner, constraint, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use core::iter::once;
+
+    #[test]
+    fn validates_signer() {
+        let mut signer = TestAccount::new([1; 32]).signer();
+        let mut other = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+
+        let mut account: Signer =
+            Signer::try_accounts(&mut context, &mut once(signer.info()), ()).unwrap();
+        assert_eq!(account.inner.key(), &[1; 32]);
+        assert_eq!(account.validate(&mut context, ()), Ok(()));
+
+        let mut account: Signer =
+            Signer::try_accounts(&mut context, &mut once(other.info()), ()).unwrap();
+        assert_eq!(
+            account
+                .validate(&mut context, ())
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountNotSigner.into())
+        );
     }
 }
@@ -0,0 +1,258 @@
+use crate::accounts::program::ExecutableProgram;
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
+use crate::programs::bpf_loader_upgradeable::{program_data_address, ProgramDataHeader};
+use crate::traits::account::{
+    Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
+};
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::program::ProgramId;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use core::fmt::Debug;
+use core::panic::Location;
+use derive_more::{Deref, DerefMut};
+use derive_where::derive_where;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+use pinocchio::pubkey::Pubkey;
+
+/// An executable program of the upgradeable loader followed by its program data account.
+///
+/// Validates the program data account is the one the program account points to.
+#[derive_where(Clone; T: Clone)]
+#[derive_where(Copy; T: Copy)]
+#[derive_where(Debug; T: Debug)]
+#[derive(Deref, DerefMut)]
+pub struct UpgradeableProgram<P: ProgramId, T = AccountInfo, E = ()> {
+    #[deref]
+    #[deref_mut]
+    program: ExecutableProgram<P, T, E>,
+    program_data: AccountInfo,
+    header: ProgramDataHeader,
+}
+impl<P: ProgramId, T, E> UpgradeableProgram<P, T, E> {
+    #[inline]
+    pub fn program(&self) -> &ExecutableProgram<P, T, E> {
+        &self.program
+    }
+
+    #[inline]
+    pub fn program_data(&self) -> &AccountInfo {
+        &self.program_data
+    }
+
+    /// `None` if the program is immutable.
+    #[inline]
+    pub fn upgrade_authority(&self) -> Option<&Pubkey> {
+        self.header.upgrade_authority.as_ref()
+    }
+
+    #[inline]
+    pub fn last_deploy_slot(&self) -> u64 {
+        self.header.slot
+    }
+}
+impl<P: ProgramId, T, E> Accounts for UpgradeableProgram<P, T, E>
+where
+    T: Accounts,
+{
+    #[inline]
+    fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        self.program
+            .to_account_metas(is_signer)
+            .chain(self.program_data.to_account_metas(is_signer))
+    }
+
+    #[inline]
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        self.program
+            .to_account_infos()
+            .chain(self.program_data.to_account_infos())
+    }
+}
+impl<P: ProgramId, T, A, E> DecodeAccounts<A> for UpgradeableProgram<P, T, E>
+where
+    T: DecodeAccounts<A>,
+    E: CustomErrorGenerator + Default,
+{
+    fn try_accounts(
+        accounts_context: &mut AccountsContext,
+        accounts: &mut impl Iterator<Item = AccountInfo>,
+        arg: A,
+    ) -> AnchorResult<Self> {
+        let location = Location::caller();
+        let program = ExecutableProgram::try_accounts(accounts_context, accounts, arg)?;
+        let program_data = AccountInfo::try_accounts(accounts_context, accounts, ())?;
+        let header = ProgramDataHeader::parse(&program_data).map_err(|error| {
+            E::default()
+                .generate(error)
+                .logged(location, Some(program_data.key()))
+        })?;
+        Ok(Self {
+            program,
+            program_data,
+            header,
+        })
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        let (lower, upper) = T::size_hint();
+        (
+            lower.saturating_add(1),
+            upper.and_then(|upper| upper.checked_add(1)),
+        )
+    }
+}
+impl<P: ProgramId, T, A, E> ValidateAccounts<A> for UpgradeableProgram<P, T, E>
+where
+    T: ValidateAccounts<A> + SingleAccount,
+    E: CustomErrorGenerator + Default,
+{
+    fn validate(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        self.program.validate(accounts_context, arg)?;
+        let error = match program_data_address(self.program.account_info_ref()) {
+            Ok(expected) if self.program_data.key() == &expected => None,
+            Ok(expected) => Some(
+                AnchorError::from(ErrorCode::AccountNotProgramData)
+                    .with_pubkeys(*self.program_data.key(), expected),
+            ),
+            Err(error) => Some(error),
+        };
+        match error {
+            None => Ok(()),
+            Some(error) => accounts_context.report::<Self>(
+                E::default().generate(error),
+                Location::caller(),
+                Some(self.program_data.key()),
+            ),
+        }
+    }
+}
+impl<P: ProgramId, T, A, E> CleanupAccounts<A> for UpgradeableProgram<P, T, E>
+where
+    T: CleanupAccounts<A>,
+{
+    #[inline]
+    fn cleanup(&mut self, accounts_context: &mut AccountsContext, arg: A) -> AnchorResult {
+        self.program.cleanup(accounts_context, arg)
+    }
+}
+impl<P: ProgramId, T, E> SignerSeeds for UpgradeableProgram<P, T, E>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        self.program.signer_seeds(buffer)
+    }
+}
+impl<P: ProgramId, T, C, E> SupportsConstraint<C> for UpgradeableProgram<P, T, E>
+where
+    T: SupportsConstraint<C>,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        self.program.early_validation(constraint, context)
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        self.program.late_validation(constraint, context)
+    }
+
+    #[inline]
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        SupportsConstraint::cleanup(&mut self.program, constraint, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::programs::bpf_loader_upgradeable::BpfLoaderUpgradeable;
+    use crate::test_util::TestAccount;
+    use crate::traits::maybe_bool::False;
+
+    struct Token;
+    impl ProgramId for Token {
+        const ID: Pubkey = [5; 32];
+
+        type IsCurrentProgram = False;
+    }
+
+    /// A program account `key` pointing to the program data account `[7; 32]`.
+    fn program(key: Pubkey) -> TestAccount {
+        let mut data = [0; 36];
+        data[..4].copy_from_slice(&2u32.to_le_bytes());
+        data[4..].copy_from_slice(&[7; 32]);
+        TestAccount::new(key)
+            .executable()
+            .owner(BpfLoaderUpgradeable::ID)
+            .data(&data)
+    }
+
+    /// A program data account `key` deployed at slot 42 with upgrade authority `[9; 32]`.
+    fn program_data(key: Pubkey) -> TestAccount {
+        let mut data = [0; ProgramDataHeader::LEN];
+        data[..4].copy_from_slice(&3u32.to_le_bytes());
+        data[4..12].copy_from_slice(&42u64.to_le_bytes());
+        data[12] = 1;
+        data[13..].copy_from_slice(&[9; 32]);
+        TestAccount::new(key)
+            .owner(BpfLoaderUpgradeable::ID)
+            .data(&data)
+    }
+
+    fn validate(program: &mut TestAccount, program_data: &mut TestAccount) -> AnchorResult {
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut upgradeable = UpgradeableProgram::<Token>::try_accounts(
+            &mut context,
+            &mut [program.info(), program_data.info()].into_iter(),
+            (),
+        )?;
+        assert_eq!(upgradeable.upgrade_authority(), Some(&[9; 32]));
+        assert_eq!(upgradeable.last_deploy_slot(), 42);
+        upgradeable.validate(&mut context, ())
+    }
+
+    #[test]
+    fn validates_program_and_program_data() {
+        assert_eq!(
+            validate(&mut program([5; 32]), &mut program_data([7; 32])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_program() {
+        assert_eq!(
+            validate(&mut program([6; 32]), &mut program_data([7; 32])),
+            Err(AnchorError::from(ErrorCode::InvalidProgramId).with_pubkeys([6; 32], [5; 32]))
+        );
+        let mut not_executable = TestAccount::new([5; 32]).owner(BpfLoaderUpgradeable::ID);
+        assert_eq!(
+            validate(&mut not_executable, &mut program_data([7; 32])).map_err(|error| error.error),
+            Err(ErrorCode::InvalidProgramExecutable.into())
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_program_data() {
+        assert_eq!(
+            validate(&mut program([5; 32]), &mut program_data([8; 32])),
+            Err(AnchorError::from(ErrorCode::AccountNotProgramData).with_pubkeys([8; 32], [7; 32]))
+        );
+    }
+}
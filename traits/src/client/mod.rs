@@ -0,0 +1,354 @@
+//! Off-chain decoding of program errors from transaction logs and custom error codes.
+
+pub mod pda;
+
+use crate::error::ErrorCode;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use pinocchio::program_error::ProgramError;
+use thiserror::Error;
+
+/// Name and message of an error code.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ErrorInfo {
+    pub name: &'static str,
+    pub message: &'static str,
+}
+
+/// Resolves custom error codes of a program, usually implemented for its error enum.
+pub trait ErrorRegistry {
+    fn lookup(code: u32) -> Option<ErrorInfo>;
+}
+impl ErrorRegistry for () {
+    #[inline]
+    fn lookup(_code: u32) -> Option<ErrorInfo> {
+        None
+    }
+}
+impl ErrorRegistry for ErrorCode {
+    fn lookup(code: u32) -> Option<ErrorInfo> {
+        ErrorCode::from_u32(code).map(|code| ErrorInfo {
+            name: code.name(),
+            message: code.message(),
+        })
+    }
+}
+/// Tries `A` and then `B`.
+impl<A, B> ErrorRegistry for (A, B)
+where
+    A: ErrorRegistry,
+    B: ErrorRegistry,
+{
+    #[inline]
+    fn lookup(code: u32) -> Option<ErrorInfo> {
+        A::lookup(code).or_else(|| B::lookup(code))
+    }
+}
+
+/// Where an error was raised, as far as the logs tell.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorOrigin {
+    /// The program that failed.
+    pub program_id: Option<String>,
+    pub account_name: Option<String>,
+    /// Source location as `file:line`.
+    pub location: Option<String>,
+    /// Base58 left and right values of a failed comparison.
+    pub compared_pubkeys: Option<(String, String)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+#[error("{}: {}", name.as_deref().unwrap_or("Unknown"), message.as_deref().unwrap_or("unknown error"))]
+pub struct ClientError {
+    /// The error number, [`ProgramError::Custom`] codes are below [`u32::MAX`].
+    pub number: u64,
+    pub name: Option<String>,
+    pub message: Option<String>,
+    pub origin: ErrorOrigin,
+}
+impl ClientError {
+    /// Resolves a custom error code against `R`, falling back to [`ErrorCode`].
+    pub fn from_code<R: ErrorRegistry>(code: u32) -> Self {
+        let info = <(R, ErrorCode)>::lookup(code);
+        Self {
+            number: code.into(),
+            name: info.map(|info| info.name.to_owned()),
+            message: info.map(|info| info.message.to_owned()),
+            origin: ErrorOrigin::default(),
+        }
+    }
+
+    fn from_number<R: ErrorRegistry>(number: u64) -> Self {
+        match u32::try_from(number) {
+            Ok(code) => Self::from_code::<R>(code),
+            Err(_) => Self {
+                number,
+                name: None,
+                message: None,
+                origin: ErrorOrigin::default(),
+            },
+        }
+    }
+
+    pub fn from_program_error<R: ErrorRegistry>(error: ProgramError) -> Self {
+        match error {
+            ProgramError::Custom(code) => Self::from_code::<R>(code),
+            error => Self {
+                number: error.into(),
+                name: Some(alloc::format!("{error:?}")),
+                message: None,
+                origin: ErrorOrigin::default(),
+            },
+        }
+    }
+
+    /// The custom error code if this is one.
+    #[inline]
+    pub fn custom_code(&self) -> Option<u32> {
+        u32::try_from(self.number).ok()
+    }
+
+    /// Parses the logs of a failed transaction.
+    ///
+    /// Prefers the error lines logged by the program, `AnchorError ...` or `ProgramError ...`,
+    /// and falls back to the runtime's `Program <id> failed: custom program error: 0x..` line.
+    /// Codes without a logged name or message are resolved against `R`.
+    pub fn from_logs<R, L>(logs: impl IntoIterator<Item = L>) -> Option<Self>
+    where
+        R: ErrorRegistry,
+        L: AsRef<str>,
+    {
+        let mut error: Option<Self> = None;
+        let mut pending = Pending::None;
+        for line in logs {
+            let line = line.as_ref();
+            if let Some((program_id, number)) = parse_failed_line(line) {
+                let error = error.get_or_insert_with(|| Self::from_number::<R>(number));
+                error
+                    .origin
+                    .program_id
+                    .get_or_insert_with(|| program_id.to_owned());
+                break;
+            }
+            let Some(log) = line.strip_prefix("Program log: ") else {
+                continue;
+            };
+            // Compared pubkeys are logged as `Left:`, key, `Right:`, key.
+            pending = match (pending, log) {
+                (Pending::None, "Left:") => Pending::LeftLabel,
+                (Pending::LeftLabel, left) => Pending::Left(left.to_owned()),
+                (Pending::Left(left), "Right:") => Pending::RightLabel(left),
+                (Pending::RightLabel(left), right) => {
+                    if let Some(error) = &mut error {
+                        error.origin.compared_pubkeys = Some((left, right.to_owned()));
+                    }
+                    Pending::None
+                }
+                _ => {
+                    if let Some(location) = log.strip_prefix("Location: ") {
+                        if let Some(error) = &mut error {
+                            error.origin.location = Some(location.trim_end_matches('.').to_owned());
+                        }
+                    } else if let Some(parsed) = parse_error_line::<R>(log) {
+                        error = Some(parsed);
+                    }
+                    Pending::None
+                }
+            };
+        }
+        error
+    }
+}
+
+enum Pending {
+    None,
+    LeftLabel,
+    Left(String),
+    RightLabel(String),
+}
+
+/// Parses `Program <id> failed: custom program error: 0x<code>`.
+fn parse_failed_line(line: &str) -> Option<(&str, u64)> {
+    let (program_id, code) = line
+        .strip_prefix("Program ")?
+        .split_once(" failed: custom program error: 0x")?;
+    Some((program_id, u64::from_str_radix(code.trim(), 16).ok()?))
+}
+
+/// Parses `{AnchorError|ProgramError} {occurred|thrown in <location>|caused by account: <name>}.
+/// Error Code: <name>. Error Number: <number>[. Error Message: <message>].`
+fn parse_error_line<R: ErrorRegistry>(log: &str) -> Option<ClientError> {
+    let rest = log
+        .strip_prefix("AnchorError ")
+        .or_else(|| log.strip_prefix("ProgramError "))?;
+    let (origin, rest) = rest.split_once(". Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (number, message) = match rest.split_once(". Error Message: ") {
+        Some((number, message)) => (number, Some(message.strip_suffix('.').unwrap_or(message))),
+        None => (rest.strip_suffix('.').unwrap_or(rest), None),
+    };
+    let number = number.parse().ok()?;
+
+    let mut error = ClientError::from_number::<R>(number);
+    error.name = Some(name.to_owned());
+    if let Some(message) = message {
+        error.message = Some(message.to_owned());
+    }
+    if let Some(location) = origin.strip_prefix("thrown in ") {
+        error.origin.location = Some(location.to_owned());
+    } else if let Some(account_name) = origin.strip_prefix("caused by account: ") {
+        error.origin.account_name = Some(account_name.to_owned());
+    }
+    Some(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    const PROGRAM: &str = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS";
+    const CALLEE: &str = "4ZQJ5hYcg7aXgFk8Dm3oS7JkxT1gCxpyCSqRkDgeYPjb";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const LEFT: &str = "7vQh1tRgexH3J2Wq7YqPAJ4XgYQWj5t5YmkV3CK2D5ds";
+    const RIGHT: &str = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin";
+
+    /// A program error enum with one variant, `6000`.
+    struct ProgramErrors;
+    impl ErrorRegistry for ProgramErrors {
+        fn lookup(code: u32) -> Option<ErrorInfo> {
+            (code == 6000).then_some(ErrorInfo {
+                name: "InsufficientFunds",
+                message: "Not enough funds",
+            })
+        }
+    }
+
+    #[test]
+    fn anchor_error_thrown_in() {
+        let logs = [
+            alloc::format!("Program {PROGRAM} invoke [1]"),
+            "Program log: Instruction: Withdraw".to_owned(),
+            "Program log: AnchorError thrown in programs/vault/src/lib.rs:42. Error Code: \
+             InsufficientFunds. Error Number: 6000. Error Message: Not enough funds."
+                .to_owned(),
+            alloc::format!("Program {PROGRAM} consumed 5403 of 200000 compute units"),
+            alloc::format!("Program {PROGRAM} failed: custom program error: 0x1770"),
+        ];
+        let error = ClientError::from_logs::<(), _>(logs).unwrap();
+        assert_eq!(error.number, 6000);
+        assert_eq!(error.name.as_deref(), Some("InsufficientFunds"));
+        assert_eq!(error.message.as_deref(), Some("Not enough funds"));
+        assert_eq!(
+            error.origin,
+            ErrorOrigin {
+                program_id: Some(PROGRAM.to_owned()),
+                account_name: None,
+                location: Some("programs/vault/src/lib.rs:42".to_owned()),
+                compared_pubkeys: None,
+            }
+        );
+    }
+
+    #[test]
+    fn anchor_error_caused_by_account() {
+        let logs = [
+            alloc::format!("Program {PROGRAM} invoke [1]"),
+            "Program log: Instruction: Withdraw".to_owned(),
+            "Program log: AnchorError caused by account: vault. Error Code: ConstraintHasOne. \
+             Error Number: 2001. Error Message: A has one constraint was violated."
+                .to_owned(),
+            "Program log: Left:".to_owned(),
+            alloc::format!("Program log: {LEFT}"),
+            "Program log: Right:".to_owned(),
+            alloc::format!("Program log: {RIGHT}"),
+            alloc::format!("Program {PROGRAM} consumed 6102 of 200000 compute units"),
+            alloc::format!("Program {PROGRAM} failed: custom program error: 0x7d1"),
+        ];
+        let error = ClientError::from_logs::<(), _>(logs).unwrap();
+        assert_eq!(
+            error.custom_code(),
+            Some(ErrorCode::ConstraintHasOne as u32)
+        );
+        assert_eq!(error.name.as_deref(), Some("ConstraintHasOne"));
+        assert_eq!(
+            error.origin,
+            ErrorOrigin {
+                program_id: Some(PROGRAM.to_owned()),
+                account_name: Some("vault".to_owned()),
+                location: None,
+                compared_pubkeys: Some((LEFT.to_owned(), RIGHT.to_owned())),
+            }
+        );
+    }
+
+    #[test]
+    fn raw_custom_program_error() {
+        let logs = [
+            alloc::format!("Program {PROGRAM} invoke [1]"),
+            alloc::format!("Program {PROGRAM} consumed 1200 of 200000 compute units"),
+            alloc::format!("Program {PROGRAM} failed: custom program error: 0xbc4"),
+        ];
+        let error = ClientError::from_logs::<(), _>(&logs).unwrap();
+        assert_eq!(error.number, 3012);
+        assert_eq!(error.name.as_deref(), Some("AccountNotInitialized"));
+        assert_eq!(error.origin.program_id.as_deref(), Some(PROGRAM));
+
+        let error = ClientError::from_logs::<ProgramErrors, _>([alloc::format!(
+            "Program {PROGRAM} failed: custom program error: 0x1770"
+        )])
+        .unwrap();
+        assert_eq!(error.name.as_deref(), Some("InsufficientFunds"));
+        assert_eq!(error.message.as_deref(), Some("Not enough funds"));
+    }
+
+    #[test]
+    fn nested_cpi_failure_is_attributed_to_the_callee() {
+        let logs = [
+            alloc::format!("Program {PROGRAM} invoke [1]"),
+            "Program log: Instruction: Deposit".to_owned(),
+            alloc::format!("Program {CALLEE} invoke [2]"),
+            "Program log: Instruction: Lock".to_owned(),
+            "Program log: AnchorError thrown in programs/lock/src/lib.rs:17. Error Code: \
+             InsufficientFunds. Error Number: 6000. Error Message: Not enough funds."
+                .to_owned(),
+            alloc::format!("Program {CALLEE} consumed 3120 of 185000 compute units"),
+            alloc::format!("Program {CALLEE} failed: custom program error: 0x1770"),
+            alloc::format!("Program {PROGRAM} consumed 18000 of 200000 compute units"),
+            alloc::format!("Program {PROGRAM} failed: custom program error: 0x1770"),
+        ];
+        let error = ClientError::from_logs::<(), _>(logs).unwrap();
+        assert_eq!(error.number, 6000);
+        assert_eq!(error.origin.program_id.as_deref(), Some(CALLEE));
+        assert_eq!(
+            error.origin.location.as_deref(),
+            Some("programs/lock/src/lib.rs:17")
+        );
+    }
+
+    #[test]
+    fn non_anchor_program_failure() {
+        let logs = [
+            alloc::format!("Program {TOKEN} invoke [1]"),
+            "Program log: Instruction: Transfer".to_owned(),
+            "Program log: Error: insufficient funds".to_owned(),
+            alloc::format!("Program {TOKEN} consumed 4381 of 200000 compute units"),
+            alloc::format!("Program {TOKEN} failed: custom program error: 0x1"),
+        ];
+        let error = ClientError::from_logs::<(), _>(logs).unwrap();
+        assert_eq!(error.custom_code(), Some(1));
+        assert_eq!(error.name, None);
+        assert_eq!(error.message, None);
+        assert_eq!(error.origin.program_id.as_deref(), Some(TOKEN));
+        assert_eq!(error.to_string(), "Unknown: unknown error");
+    }
+
+    #[test]
+    fn no_failure() {
+        let logs = [
+            alloc::format!("Program {PROGRAM} invoke [1]"),
+            alloc::format!("Program {PROGRAM} success"),
+        ];
+        assert_eq!(ClientError::from_logs::<(), _>(logs), None);
+    }
+}
@@ -0,0 +1,342 @@
+//! Host implementation of program derived addresses, matching the runtime's syscalls.
+
+use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use sha2::{Digest, Sha256};
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Derives the address of `seeds`, which must not be a valid ed25519 point.
+pub fn create_program_address(
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<Pubkey, ProgramError> {
+    if seeds.len() > MAX_SEEDS {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+    let address: Pubkey = hasher.finalize().into();
+
+    if is_on_curve(&address) {
+        Err(ProgramError::InvalidSeeds)
+    } else {
+        Ok(address)
+    }
+}
+
+/// Finds the address and canonical bump of `seeds`, the highest bump that is off the curve.
+pub fn try_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    // The bump takes one of the seeds.
+    if seeds.len() >= MAX_SEEDS {
+        return None;
+    }
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut seeds_with_bump = [&[][..]; MAX_SEEDS];
+        seeds_with_bump[..seeds.len()].copy_from_slice(seeds);
+        seeds_with_bump[seeds.len()] = &bump_seed;
+        if let Ok(address) = create_program_address(&seeds_with_bump[..=seeds.len()], program_id) {
+            return Some((address, bump));
+        }
+    }
+    None
+}
+
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    try_find_program_address(seeds, program_id)
+        .unwrap_or_else(|| panic!("Unable to find a viable program address bump seed"))
+}
+
+/// Whether `bytes` decompress to a point on the ed25519 curve.
+///
+/// A compressed point is the `y` coordinate and the sign of `x`. It decompresses if
+/// `x^2 = (y^2 - 1) / (d * y^2 + 1)` has a solution, so if `(y^2 - 1) * (d * y^2 + 1)` is a
+/// square. Non-canonical `y` is reduced like the runtime does.
+pub fn is_on_curve(bytes: &[u8; 32]) -> bool {
+    let y = FieldElement::from_bytes(bytes);
+    let y2 = y.square();
+    let u = y2.sub(&FieldElement::ONE);
+    let v = FieldElement::D.mul(&y2).add(&FieldElement::ONE);
+    let legendre = u.mul(&v).pow_p_minus_1_over_2();
+    legendre.is_zero() || legendre == FieldElement::ONE
+}
+
+/// An element of the field modulo `2^255 - 19` in radix `2^51`.
+#[derive(Copy, Clone, Debug)]
+struct FieldElement([u64; 5]);
+impl FieldElement {
+    const MASK: u64 = (1 << 51) - 1;
+    const ZERO: Self = Self([0; 5]);
+    const ONE: Self = Self([1, 0, 0, 0, 0]);
+    /// `-121665 / 121666`
+    const D: Self = Self([
+        929955233495203,
+        466365720129213,
+        1662059464998953,
+        2033849074728123,
+        1442794654840575,
+    ]);
+
+    /// Reads 255 little endian bits, ignoring the sign bit.
+    fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let word = |i: usize| {
+            let mut word = [0; 8];
+            word.copy_from_slice(&bytes[i * 8..][..8]);
+            u64::from_le_bytes(word)
+        };
+        let (w0, w1, w2, w3) = (word(0), word(1), word(2), word(3));
+        Self([
+            w0 & Self::MASK,
+            (w0 >> 51 | w1 << 13) & Self::MASK,
+            (w1 >> 38 | w2 << 26) & Self::MASK,
+            (w2 >> 25 | w3 << 39) & Self::MASK,
+            (w3 >> 12) & Self::MASK,
+        ])
+    }
+
+    /// Carries limbs above 51 bits, wrapping `2^255` around as `19`.
+    fn carry(mut limbs: [u128; 5]) -> Self {
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= Self::MASK as u128;
+        }
+        limbs[0] += (limbs[4] >> 51) * 19;
+        limbs[4] &= Self::MASK as u128;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= Self::MASK as u128;
+        Self(limbs.map(|limb| limb as u64))
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self::carry(core::array::from_fn(|i| {
+            u128::from(self.0[i]) + u128::from(other.0[i])
+        }))
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        // Adds `2p` so limbs don't underflow.
+        const TWO_P: [u64; 5] = [
+            2 * (FieldElement::MASK - 18),
+            2 * FieldElement::MASK,
+            2 * FieldElement::MASK,
+            2 * FieldElement::MASK,
+            2 * FieldElement::MASK,
+        ];
+        Self::carry(core::array::from_fn(|i| {
+            u128::from(self.0[i]) + u128::from(TWO_P[i]) - u128::from(other.0[i])
+        }))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let a = self.0.map(u128::from);
+        let b = other.0.map(u128::from);
+        let mut limbs = [0; 5];
+        for i in 0..5 {
+            for j in 0..5 {
+                let product = a[i] * b[j];
+                if i + j < 5 {
+                    limbs[i + j] += product;
+                } else {
+                    limbs[i + j - 5] += product * 19;
+                }
+            }
+        }
+        Self::carry(limbs)
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// `self^((p - 1) / 2)`, `1` for non zero squares and `-1` otherwise.
+    fn pow_p_minus_1_over_2(&self) -> Self {
+        // (p - 1) / 2 = 2^254 - 10, bits 253 to 4 set then 0b0110.
+        let mut out = Self::ONE;
+        for bit in (0..254).rev() {
+            out = out.square();
+            if bit >= 4 || (0b0110 >> bit) & 1 == 1 {
+                out = out.mul(self);
+            }
+        }
+        out
+    }
+
+    /// The limbs fully reduced below `p`.
+    fn reduce(&self) -> [u64; 5] {
+        let mut limbs = Self::carry(self.0.map(u128::from)).0;
+        // One if the value is at least `p`.
+        let mut q = (limbs[0] + 19) >> 51;
+        for limb in &limbs[1..] {
+            q = (limb + q) >> 51;
+        }
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= Self::MASK;
+        }
+        limbs[4] &= Self::MASK;
+        limbs
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.reduce() == other.reduce()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pinocchio_pubkey::from_str;
+
+    const BPF_LOADER_UPGRADEABLE: Pubkey = from_str("BPFLoaderUpgradeab1e11111111111111111111111");
+
+    #[test]
+    fn matches_known_program_addresses() {
+        let seed_key = from_str("SeedPubey1111111111111111111111111111111111");
+        let cases: [(&[&[u8]], Pubkey); 4] = [
+            (
+                &[b"", &[1]],
+                from_str("BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe"),
+            ),
+            (
+                &["☉".as_bytes(), &[0]],
+                from_str("13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19"),
+            ),
+            (
+                &[b"Talking", b"Squirrels"],
+                from_str("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk"),
+            ),
+            (
+                &[&seed_key, &[1]],
+                from_str("976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL"),
+            ),
+        ];
+        for (seeds, expected) in cases {
+            assert_eq!(
+                create_program_address(seeds, &BPF_LOADER_UPGRADEABLE),
+                Ok(expected)
+            );
+            assert!(!is_on_curve(&expected));
+        }
+    }
+
+    #[test]
+    fn finds_canonical_bump() {
+        let seeds: &[&[u8]] = &[b"Lil'", b"Bits"];
+        let (address, bump) = find_program_address(seeds, &BPF_LOADER_UPGRADEABLE);
+        assert_eq!(
+            create_program_address(&[b"Lil'", b"Bits", &[bump]], &BPF_LOADER_UPGRADEABLE),
+            Ok(address)
+        );
+        for higher in (0..=u8::MAX).filter(|&higher| higher > bump) {
+            assert_eq!(
+                create_program_address(&[b"Lil'", b"Bits", &[higher]], &BPF_LOADER_UPGRADEABLE),
+                Err(ProgramError::InvalidSeeds)
+            );
+        }
+    }
+
+    #[test]
+    fn derives_ata_and_metadata_addresses() {
+        let token_program = from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        let ata_program = from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+        let metadata_program = from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+        let loader = from_str("BPFLoaderUpgradeab1e11111111111111111111111");
+        let wallet = from_str("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+        let mint = from_str("EPjFWdd5AufqSSqeM2qspzP6bEMGnjR5wk5FEaBhV7zm");
+
+        // The USDC account of `wallet`.
+        assert_eq!(
+            find_program_address(&[&wallet, &token_program, &mint], &ata_program),
+            (
+                from_str("DqaiC4nN9pyuTrQur2vBecVuuigaPKxD2ePJwvc1Nzcu"),
+                255
+            )
+        );
+        // The metadata of USDC.
+        assert_eq!(
+            find_program_address(&[b"metadata", &metadata_program, &mint], &metadata_program),
+            (
+                from_str("EjppuKai7URJw7x5LDMcue3tP8ejBHwWNoxSwjA2kVw9"),
+                253
+            )
+        );
+        // The program data of the metadata program.
+        assert_eq!(
+            find_program_address(&[&metadata_program], &loader),
+            (from_str("PwDiXFxQsGra4sFFTT8r1QWRMd4vfumiWC1jfWNfdYT"), 253)
+        );
+        assert_eq!(
+            create_program_address(&[&metadata_program, &[253]], &loader),
+            Ok(from_str("PwDiXFxQsGra4sFFTT8r1QWRMd4vfumiWC1jfWNfdYT"))
+        );
+    }
+
+    #[test]
+    fn wallet_keys_are_on_curve() {
+        // The ed25519 base point and the RFC 8032 test 1 public key.
+        let mut base_point = [0x66; 32];
+        base_point[0] = 0x58;
+        assert!(is_on_curve(&base_point));
+        assert!(is_on_curve(&[
+            0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+            0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+            0xf7, 0x07, 0x51, 0x1a,
+        ]));
+        // The identity point, `y = 1`.
+        let mut identity = [0; 32];
+        identity[0] = 1;
+        assert!(is_on_curve(&identity));
+        // `y = 2` has no matching `x`.
+        let mut off_curve = [0; 32];
+        off_curve[0] = 2;
+        assert!(!is_on_curve(&off_curve));
+    }
+
+    #[test]
+    fn rejects_too_many_seeds() {
+        let seeds = [&b"seed"[..]; MAX_SEEDS + 1];
+        assert_eq!(
+            create_program_address(&seeds, &BPF_LOADER_UPGRADEABLE),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+        // The bump needs a seed slot of its own.
+        assert_eq!(
+            try_find_program_address(&seeds[..MAX_SEEDS], &BPF_LOADER_UPGRADEABLE),
+            None
+        );
+        assert!(
+            try_find_program_address(&seeds[..MAX_SEEDS - 1], &BPF_LOADER_UPGRADEABLE).is_some()
+        );
+    }
+
+    #[test]
+    fn rejects_long_seeds() {
+        let long = [0; MAX_SEED_LEN + 1];
+        assert_eq!(
+            create_program_address(&[&long], &BPF_LOADER_UPGRADEABLE),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+        assert_eq!(
+            try_find_program_address(&[&long], &BPF_LOADER_UPGRADEABLE),
+            None
+        );
+        assert!(
+            try_find_program_address(&[&long[..MAX_SEED_LEN]], &BPF_LOADER_UPGRADEABLE).is_some()
+        );
+    }
+}
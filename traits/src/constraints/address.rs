@@ -1,22 +1,43 @@
-use crate::error::AnchorResult;
-use crate::traits::constraint::SupportsConstraint;
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
 use crate::traits::AccountsContext;
 use pinocchio::account_info::AccountInfo;
-use pinocchio::program_error::ProgramError;
 use pinocchio::pubkey::Pubkey;
 
 #[derive(Copy, Clone, Debug)]
 pub struct Address(pub Pubkey);
-impl SupportsConstraint<Address> for AccountInfo {
+impl AccountInfoConstraint for Address {
     fn late_validation(
         &mut self,
-        constraint: &mut Address,
+        account: &AccountInfo,
         _context: &mut AccountsContext,
     ) -> AnchorResult {
-        if self.key() == &constraint.0 {
+        if account.key() == &self.0 {
             Ok(())
         } else {
-            Err(ProgramError::InvalidArgument)
+            Err(AnchorError::from(ErrorCode::ConstraintAddress)
+                .with_pubkeys(*account.key(), self.0))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn address() {
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            Address([1; 32]).late_validation(&info, &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            Address([2; 32]).late_validation(&info, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintAddress).with_pubkeys([1; 32], [2; 32]))
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::AnchorResult;
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+/// Every constraint in the tuple must pass. Unlike a bare tuple this is an
+/// [`AccountInfoConstraint`], so it can be nested in [`Any`](crate::constraints::any::Any),
+/// [`Not`](crate::constraints::not::Not) or given a custom error.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct All<C>(pub C);
+
+macro_rules! impl_all_tuple {
+    ($($constraint:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($constraint),+> AccountInfoConstraint for All<($($constraint,)+)>
+        where
+            $($constraint: AccountInfoConstraint,)+
+        {
+            fn early_validation(
+                &mut self,
+                account: &AccountInfo,
+                context: &mut AccountsContext,
+            ) -> AnchorResult {
+                let ($($constraint,)+) = &mut self.0;
+                $($constraint.early_validation(account, context)?;)+
+                Ok(())
+            }
+
+            fn late_validation(
+                &mut self,
+                account: &AccountInfo,
+                context: &mut AccountsContext,
+            ) -> AnchorResult {
+                let ($($constraint,)+) = &mut self.0;
+                $($constraint.late_validation(account, context)?;)+
+                Ok(())
+            }
+
+            fn cleanup(
+                &mut self,
+                account: &AccountInfo,
+                context: &mut AccountsContext,
+            ) -> AnchorResult {
+                let ($($constraint,)+) = &mut self.0;
+                $($constraint.cleanup(account, context)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+impl_all_tuple!(C1);
+impl_all_tuple!(C1, C2);
+impl_all_tuple!(C1, C2, C3);
+impl_all_tuple!(C1, C2, C3, C4);
+impl_all_tuple!(C1, C2, C3, C4, C5);
+impl_all_tuple!(C1, C2, C3, C4, C5, C6);
+impl_all_tuple!(C1, C2, C3, C4, C5, C6, C7);
+impl_all_tuple!(C1, C2, C3, C4, C5, C6, C7, C8);
+
+/// Implements [`SupportsConstraint`] for tuples of the constraints an account supports, every
+/// constraint must pass. Used by the innermost accounts, wrappers forward tuples like any other
+/// constraint.
+macro_rules! impl_supports_constraint_tuples {
+    (impl[$($generics:tt)*] $account:ty where [$($bounds:tt)*]) => {
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3, C4
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3, C4, C5
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3, C4, C5, C6
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3, C4, C5, C6, C7
+        );
+        $crate::constraints::all::impl_supports_constraint_tuples!(
+            @tuple [$($generics)*] $account [$($bounds)*] C1, C2, C3, C4, C5, C6, C7, C8
+        );
+    };
+    (@tuple [$($generics:tt)*] $account:ty [$($bounds:tt)*] $($constraint:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($generics)* $($constraint),+>
+            $crate::traits::constraint::SupportsConstraint<($($constraint,)+)> for $account
+        where
+            $($bounds)*
+            $(Self: $crate::traits::constraint::SupportsConstraint<$constraint>,)+
+        {
+            fn early_validation(
+                &mut self,
+                constraint: &mut ($($constraint,)+),
+                context: &mut $crate::traits::AccountsContext,
+            ) -> $crate::error::AnchorResult {
+                let ($($constraint,)+) = constraint;
+                $($crate::traits::constraint::SupportsConstraint::<$constraint>::early_validation(
+                    self,
+                    $constraint,
+                    context,
+                )?;)+
+                Ok(())
+            }
+
+            fn late_validation(
+                &mut self,
+                constraint: &mut ($($constraint,)+),
+                context: &mut $crate::traits::AccountsContext,
+            ) -> $crate::error::AnchorResult {
+                let ($($constraint,)+) = constraint;
+                $($crate::traits::constraint::SupportsConstraint::<$constraint>::late_validation(
+                    self,
+                    $constraint,
+                    context,
+                )?;)+
+                Ok(())
+            }
+
+            fn cleanup(
+                &mut self,
+                constraint: &mut ($($constraint,)+),
+                context: &mut $crate::traits::AccountsContext,
+            ) -> $crate::error::AnchorResult {
+                let ($($constraint,)+) = constraint;
+                $($crate::traits::constraint::SupportsConstraint::<$constraint>::cleanup(
+                    self,
+                    $constraint,
+                    context,
+                )?;)+
+                Ok(())
+            }
+        }
+    };
+}
+pub(crate) use impl_supports_constraint_tuples;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::check::Check;
+    use crate::constraints::is_signer::IsSigner;
+    use crate::constraints::is_writable::IsWritable;
+    use crate::error::ErrorCode;
+    use crate::test_util::{FailsEarly, TestAccount};
+    use crate::traits::constraint::SupportsConstraint;
+    use core::cell::Cell;
+
+    #[test]
+    fn every_member_must_pass() {
+        let mut both = TestAccount::new([1; 32]).signer().writable();
+        let mut signer = TestAccount::new([2; 32]).signer();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut all = All((IsSigner, IsWritable));
+        assert_eq!(all.late_validation(&both.info(), &mut context), Ok(()));
+        assert_eq!(
+            all.late_validation(&signer.info(), &mut context),
+            Err(ErrorCode::ConstraintMut.into())
+        );
+        assert_eq!(
+            All((IsSigner, FailsEarly)).early_validation(&both.info(), &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+    }
+
+    #[test]
+    fn stops_at_first_failure() {
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let runs = &Cell::new(0);
+        let run = |result: AnchorResult| {
+            Check::new(move |_: &AccountInfo, _: &AccountsContext| {
+                runs.set(runs.get() + 1);
+                result
+            })
+        };
+        let mut all = All((
+            run(Ok(())),
+            run(Err(ErrorCode::ConstraintRaw.into())),
+            run(Err(ErrorCode::ConstraintOwner.into())),
+        ));
+        assert_eq!(
+            all.late_validation(&info, &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn tuples_require_every_member() {
+        let mut both = TestAccount::new([1; 32]).signer().writable();
+        let mut writable = TestAccount::new([2; 32]).writable();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            both.info()
+                .late_validation(&mut (IsSigner, IsWritable), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            writable
+                .info()
+                .late_validation(&mut (IsSigner, IsWritable), &mut context),
+            Err(ErrorCode::ConstraintSigner.into())
+        );
+        assert_eq!(
+            both.info()
+                .early_validation(&mut (IsWritable, FailsEarly), &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+/// Runs both validation phases of `constraint`, for combinators that need its overall result.
+#[inline]
+pub(crate) fn validate_fully<C>(
+    constraint: &mut C,
+    account: &AccountInfo,
+    context: &mut AccountsContext,
+) -> AnchorResult
+where
+    C: AccountInfoConstraint,
+{
+    constraint.early_validation(account, context)?;
+    constraint.late_validation(account, context)
+}
+
+/// Wraps a tuple of constraints, passing if any constraint passes both its early and late
+/// validation. If none pass the last error is returned.
+///
+/// Every member is run during late validation, and their cleanup is not run.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Any<C>(pub C);
+
+macro_rules! impl_any_tuple {
+    ($($constraint:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($constraint),+> AccountInfoConstraint for Any<($($constraint,)+)>
+        where
+            $($constraint: AccountInfoConstraint,)+
+        {
+            fn late_validation(
+                &mut self,
+                account: &AccountInfo,
+                context: &mut AccountsContext,
+            ) -> AnchorResult {
+                let ($($constraint,)+) = &mut self.0;
+                Err(AnchorError::from(ErrorCode::ConstraintRaw))
+                    $(.or_else(|_| validate_fully($constraint, account, context)))+
+            }
+        }
+    };
+}
+impl_any_tuple!(C1);
+impl_any_tuple!(C1, C2);
+impl_any_tuple!(C1, C2, C3);
+impl_any_tuple!(C1, C2, C3, C4);
+impl_any_tuple!(C1, C2, C3, C4, C5);
+impl_any_tuple!(C1, C2, C3, C4, C5, C6);
+impl_any_tuple!(C1, C2, C3, C4, C5, C6, C7);
+impl_any_tuple!(C1, C2, C3, C4, C5, C6, C7, C8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::is_signer::IsSigner;
+    use crate::test_util::{FailsEarly, TestAccount};
+
+    #[test]
+    fn member_must_pass_both_phases() {
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut any = Any((FailsEarly, IsSigner));
+        assert_eq!(any.early_validation(&info, &mut context), Ok(()));
+        assert_eq!(
+            any.late_validation(&info, &mut context),
+            Err(ErrorCode::ConstraintSigner.into())
+        );
+    }
+
+    #[test]
+    fn any_member_passes() {
+        let mut account = TestAccount::new([1; 32]).signer();
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut any = Any((FailsEarly, IsSigner));
+        assert_eq!(any.late_validation(&info, &mut context), Ok(()));
+    }
+}
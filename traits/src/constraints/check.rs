@@ -0,0 +1,60 @@
+use crate::error::AnchorResult;
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+/// One-off constraint from a closure, run during late validation.
+#[derive(Copy, Clone, Debug)]
+pub struct Check<F>(pub F);
+impl<F> Check<F>
+where
+    F: FnMut(&AccountInfo, &AccountsContext) -> AnchorResult,
+{
+    /// Helps closure signature inference over using the tuple constructor.
+    #[inline]
+    pub fn new(check: F) -> Self {
+        Self(check)
+    }
+}
+impl<F> AccountInfoConstraint for Check<F>
+where
+    F: FnMut(&AccountInfo, &AccountsContext) -> AnchorResult,
+{
+    #[inline]
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        (self.0)(account, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn runs_closure_during_late_validation() {
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut check = Check::new(|account: &AccountInfo, _: &AccountsContext| {
+            if account.key() == &[1; 32] {
+                Ok(())
+            } else {
+                Err(ErrorCode::ConstraintRaw.into())
+            }
+        });
+        assert_eq!(check.early_validation(&info, &mut context), Ok(()));
+        assert_eq!(check.late_validation(&info, &mut context), Ok(()));
+
+        let mut other = TestAccount::new([2; 32]);
+        assert_eq!(
+            check.late_validation(&other.info(), &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+#[derive(Copy, Clone, Debug)]
+pub enum DataLen {
+    Exact(usize),
+    Min(usize),
+    Max(usize),
+}
+impl AccountInfoConstraint for DataLen {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        let data_len = account.data_len();
+        let valid = match *self {
+            DataLen::Exact(len) => data_len == len,
+            DataLen::Min(len) => data_len >= len,
+            DataLen::Max(len) => data_len <= len,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintSpace.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn data_len() {
+        let mut account = TestAccount::new([1; 32]).data(&[0; 8]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        for mut passing in [DataLen::Exact(8), DataLen::Min(8), DataLen::Max(8)] {
+            assert_eq!(passing.late_validation(&info, &mut context), Ok(()));
+        }
+        for mut failing in [DataLen::Exact(7), DataLen::Min(9), DataLen::Max(7)] {
+            assert_eq!(
+                failing.late_validation(&info, &mut context),
+                Err(ErrorCode::ConstraintSpace.into())
+            );
+        }
+    }
+}
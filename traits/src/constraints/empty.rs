@@ -0,0 +1,39 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+/// The account has no data.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Empty;
+impl AccountInfoConstraint for Empty {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if account.data_is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintZero.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn empty() {
+        let mut empty = TestAccount::new([1; 32]);
+        let mut account = TestAccount::new([2; 32]).data(&[1]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(Empty.late_validation(&empty.info(), &mut context), Ok(()));
+        assert_eq!(
+            Empty.late_validation(&account.info(), &mut context),
+            Err(ErrorCode::ConstraintZero.into())
+        );
+    }
+}
@@ -1,21 +1,41 @@
-use crate::error::AnchorResult;
-use crate::traits::constraint::SupportsConstraint;
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
 use crate::traits::AccountsContext;
 use pinocchio::account_info::AccountInfo;
-use pinocchio::program_error::ProgramError;
 
 #[derive(Copy, Clone, Debug)]
 pub struct Executable;
-impl SupportsConstraint<Executable> for AccountInfo {
+impl AccountInfoConstraint for Executable {
     fn late_validation(
         &mut self,
-        _constraint: &mut Executable,
+        account: &AccountInfo,
         _context: &mut AccountsContext,
     ) -> AnchorResult {
-        if self.executable() {
+        if account.executable() {
             Ok(())
         } else {
-            Err(ProgramError::InvalidArgument)
+            Err(ErrorCode::ConstraintExecutable.into())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn executable() {
+        let mut program = TestAccount::new([1; 32]).executable();
+        let mut account = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            Executable.late_validation(&program.info(), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            Executable.late_validation(&account.info(), &mut context),
+            Err(ErrorCode::ConstraintExecutable.into())
+        );
+    }
+}
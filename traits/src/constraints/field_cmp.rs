@@ -0,0 +1,227 @@
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
+use crate::traits::constraint::DataConstraint;
+use crate::traits::AccountsContext;
+use core::any::Any;
+use core::cmp::Ordering;
+use pinocchio::pubkey::Pubkey;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+impl Comparison {
+    /// Whether `ordering` of the field to the value satisfies the comparison.
+    pub fn matches(self, ordering: Option<Ordering>) -> bool {
+        match self {
+            Self::Eq => ordering == Some(Ordering::Equal),
+            Self::Ne => ordering != Some(Ordering::Equal),
+            Self::Lt => ordering == Some(Ordering::Less),
+            Self::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ordering == Some(Ordering::Greater),
+            Self::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+
+    pub fn error_code(self) -> ErrorCode {
+        match self {
+            Self::Eq => ErrorCode::RequireEqViolated,
+            Self::Ne => ErrorCode::RequireNeqViolated,
+            // Anchor has no less than codes, `field < value` is `value > field`.
+            Self::Gt | Self::Lt => ErrorCode::RequireGtViolated,
+            Self::Ge | Self::Le => ErrorCode::RequireGteViolated,
+        }
+    }
+
+    /// The error for a failed comparison of `field` to `value`, carrying both if they are
+    /// [`Pubkey`]s.
+    pub fn error(self, field: &dyn Any, value: &dyn Any) -> AnchorError {
+        match (
+            field.downcast_ref::<Pubkey>(),
+            value.downcast_ref::<Pubkey>(),
+        ) {
+            (Some(field), Some(value)) => AnchorError::from(match self {
+                Self::Eq => ErrorCode::RequireKeysEqViolated,
+                Self::Ne => ErrorCode::RequireKeysNeqViolated,
+                _ => self.error_code(),
+            })
+            .with_pubkeys(*field, *value),
+            _ => self.error_code().into(),
+        }
+    }
+}
+
+/// The field selected from the account data by `field` compares to `value` by `comparison`.
+#[derive(Copy, Clone, Debug)]
+pub struct FieldCmp<F, V, E = ()> {
+    pub field: F,
+    pub comparison: Comparison,
+    pub value: V,
+    pub error: E,
+}
+impl<F, V> FieldCmp<F, V> {
+    #[inline]
+    pub fn new<T>(field: F, comparison: Comparison, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self {
+            field,
+            comparison,
+            value,
+            error: (),
+        }
+    }
+
+    #[inline]
+    pub fn eq<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Eq, value)
+    }
+
+    #[inline]
+    pub fn ne<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Ne, value)
+    }
+
+    #[inline]
+    pub fn lt<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Lt, value)
+    }
+
+    #[inline]
+    pub fn le<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Le, value)
+    }
+
+    #[inline]
+    pub fn gt<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Gt, value)
+    }
+
+    #[inline]
+    pub fn ge<T>(field: F, value: V) -> Self
+    where
+        F: Fn(&T) -> &V,
+    {
+        Self::new(field, Comparison::Ge, value)
+    }
+}
+impl<F, V, E> FieldCmp<F, V, E> {
+    #[inline]
+    pub fn with_error<E2>(self, error: E2) -> FieldCmp<F, V, E2>
+    where
+        E2: CustomErrorGenerator + Clone,
+    {
+        FieldCmp {
+            field: self.field,
+            comparison: self.comparison,
+            value: self.value,
+            error,
+        }
+    }
+}
+impl<T, F, V, E> DataConstraint<T> for FieldCmp<F, V, E>
+where
+    F: Fn(&T) -> &V,
+    V: PartialOrd + 'static,
+    E: CustomErrorGenerator + Clone,
+{
+    fn validate_data(&mut self, data: &T, _context: &mut AccountsContext) -> AnchorResult {
+        let field = (self.field)(data);
+        if self.comparison.matches(field.partial_cmp(&self.value)) {
+            Ok(())
+        } else {
+            Err(self
+                .error
+                .clone()
+                .generate(self.comparison.error(field, &self.value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lock {
+        expiry: u64,
+        owner: Pubkey,
+    }
+
+    #[test]
+    fn comparisons_and_errors() {
+        let lock = Lock {
+            expiry: 10,
+            owner: [1; 32],
+        };
+        let mut context = AccountsContext::new(&[0; 32]);
+        fn expiry(lock: &Lock) -> &u64 {
+            &lock.expiry
+        }
+        let cases = [
+            (Comparison::Eq, 10, 11, ErrorCode::RequireEqViolated),
+            (Comparison::Ne, 11, 10, ErrorCode::RequireNeqViolated),
+            (Comparison::Lt, 11, 10, ErrorCode::RequireGtViolated),
+            (Comparison::Le, 10, 9, ErrorCode::RequireGteViolated),
+            (Comparison::Gt, 9, 10, ErrorCode::RequireGtViolated),
+            (Comparison::Ge, 10, 11, ErrorCode::RequireGteViolated),
+        ];
+        for (comparison, passing, failing, error) in cases {
+            assert_eq!(
+                FieldCmp::new(expiry, comparison, passing).validate_data(&lock, &mut context),
+                Ok(())
+            );
+            assert_eq!(
+                FieldCmp::new(expiry, comparison, failing).validate_data(&lock, &mut context),
+                Err(error.into())
+            );
+        }
+    }
+
+    #[test]
+    fn pubkey_errors_carry_keys() {
+        let lock = Lock {
+            expiry: 10,
+            owner: [1; 32],
+        };
+        let mut context = AccountsContext::new(&[0; 32]);
+        fn owner(lock: &Lock) -> &Pubkey {
+            &lock.owner
+        }
+        assert_eq!(
+            FieldCmp::eq(owner, [1; 32]).validate_data(&lock, &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            FieldCmp::eq(owner, [2; 32]).validate_data(&lock, &mut context),
+            Err(AnchorError::from(ErrorCode::RequireKeysEqViolated).with_pubkeys([1; 32], [2; 32]))
+        );
+        assert_eq!(
+            FieldCmp::ne(owner, [1; 32]).validate_data(&lock, &mut context),
+            Err(AnchorError::from(ErrorCode::RequireKeysNeqViolated).with_pubkeys([1; 32], [1; 32]))
+        );
+        assert_eq!(
+            FieldCmp::gt(owner, [1; 32]).validate_data(&lock, &mut context),
+            Err(AnchorError::from(ErrorCode::RequireGtViolated).with_pubkeys([1; 32], [1; 32]))
+        );
+    }
+}
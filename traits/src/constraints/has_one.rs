@@ -0,0 +1,188 @@
+use crate::error::{AnchorError, AnchorResult, CustomErrorGenerator, ErrorCode};
+use crate::traits::account::SingleAccount;
+use crate::traits::constraint::DataConstraint;
+use crate::traits::program::GetProgramId;
+use crate::traits::AccountsContext;
+use core::marker::PhantomData;
+use derive_where::derive_where;
+use pinocchio::pubkey::Pubkey;
+
+/// Where [`HasOne`] gets the key it expects.
+pub trait ExpectedKey {
+    fn expected_key(&self, context: &AccountsContext) -> Pubkey;
+}
+impl ExpectedKey for Pubkey {
+    #[inline]
+    fn expected_key(&self, _context: &AccountsContext) -> Pubkey {
+        *self
+    }
+}
+
+/// The key of another account of the instruction.
+impl<T> ExpectedKey for &T
+where
+    T: SingleAccount + ?Sized,
+{
+    #[inline]
+    fn expected_key(&self, _context: &AccountsContext) -> Pubkey {
+        *self.key()
+    }
+}
+
+/// The id of the program `P`, taken from the [`AccountsContext`] for
+/// [`CurrentProgram`](crate::traits::program::CurrentProgram).
+#[derive_where(Copy, Clone, Debug, Default)]
+pub struct ProgramKey<P>(PhantomData<fn() -> P>);
+impl<P> ExpectedKey for ProgramKey<P>
+where
+    P: GetProgramId,
+{
+    #[inline]
+    fn expected_key(&self, context: &AccountsContext) -> Pubkey {
+        *P::program_id(context)
+    }
+}
+
+/// The [`Pubkey`] selected from the account data by `field` is `key`, usually the key of another
+/// account in the instruction.
+#[derive(Copy, Clone, Debug)]
+pub struct HasOne<F, K = Pubkey, E = ()> {
+    pub field: F,
+    pub key: K,
+    pub error: E,
+}
+impl<F> HasOne<F> {
+    #[inline]
+    pub fn new<T>(field: F, key: Pubkey) -> Self
+    where
+        F: Fn(&T) -> &Pubkey,
+    {
+        Self {
+            field,
+            key,
+            error: (),
+        }
+    }
+}
+impl<'a, F, A> HasOne<F, &'a A>
+where
+    A: SingleAccount + ?Sized,
+{
+    /// Expects the key of `account`.
+    #[inline]
+    pub fn account<T>(field: F, account: &'a A) -> Self
+    where
+        F: Fn(&T) -> &Pubkey,
+    {
+        Self {
+            field,
+            key: account,
+            error: (),
+        }
+    }
+}
+impl<F, P> HasOne<F, ProgramKey<P>> {
+    /// Expects the id of the program `P`.
+    #[inline]
+    pub fn program<T>(field: F) -> Self
+    where
+        F: Fn(&T) -> &Pubkey,
+    {
+        Self {
+            field,
+            key: ProgramKey(PhantomData),
+            error: (),
+        }
+    }
+}
+impl<F, K, E> HasOne<F, K, E> {
+    #[inline]
+    pub fn with_error<E2>(self, error: E2) -> HasOne<F, K, E2>
+    where
+        E2: CustomErrorGenerator + Clone,
+    {
+        HasOne {
+            field: self.field,
+            key: self.key,
+            error,
+        }
+    }
+}
+impl<T, F, K, E> DataConstraint<T> for HasOne<F, K, E>
+where
+    F: Fn(&T) -> &Pubkey,
+    K: ExpectedKey,
+    E: CustomErrorGenerator + Clone,
+{
+    fn validate_data(&mut self, data: &T, context: &mut AccountsContext) -> AnchorResult {
+        let field = (self.field)(data);
+        let key = self.key.expected_key(context);
+        if field == &key {
+            Ok(())
+        } else {
+            Err(self
+                .error
+                .clone()
+                .generate(AnchorError::from(ErrorCode::ConstraintHasOne).with_pubkeys(*field, key)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use crate::traits::program::CurrentProgram;
+
+    struct Vault {
+        authority: Pubkey,
+    }
+
+    #[test]
+    fn key_from_value() {
+        let vault = Vault { authority: [2; 32] };
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut has_one = HasOne::new(|vault: &Vault| &vault.authority, [2; 32]);
+        assert_eq!(has_one.validate_data(&vault, &mut context), Ok(()));
+        has_one.key = [3; 32];
+        assert_eq!(
+            has_one.validate_data(&vault, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintHasOne).with_pubkeys([2; 32], [3; 32]))
+        );
+    }
+
+    #[test]
+    fn key_from_context() {
+        let vault = Vault { authority: [2; 32] };
+        let mut has_one =
+            HasOne::<_, ProgramKey<CurrentProgram>>::program(|vault: &Vault| &vault.authority);
+        assert_eq!(
+            has_one.validate_data(&vault, &mut AccountsContext::new(&[2; 32])),
+            Ok(())
+        );
+        assert_eq!(
+            has_one.validate_data(&vault, &mut AccountsContext::new(&[3; 32])),
+            Err(AnchorError::from(ErrorCode::ConstraintHasOne).with_pubkeys([2; 32], [3; 32]))
+        );
+    }
+
+    #[test]
+    fn key_from_account() {
+        let vault = Vault { authority: [2; 32] };
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut authority = TestAccount::new([2; 32]);
+        let authority = authority.info();
+        assert_eq!(
+            HasOne::account(|vault: &Vault| &vault.authority, &authority)
+                .validate_data(&vault, &mut context),
+            Ok(())
+        );
+        let mut other = TestAccount::new([3; 32]);
+        let other = other.info();
+        assert_eq!(
+            HasOne::account(|vault: &Vault| &vault.authority, &other)
+                .validate_data(&vault, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintHasOne).with_pubkeys([2; 32], [3; 32]))
+        );
+    }
+}
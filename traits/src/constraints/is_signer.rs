@@ -0,0 +1,41 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct IsSigner;
+impl AccountInfoConstraint for IsSigner {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if account.is_signer() {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintSigner.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn is_signer() {
+        let mut signer = TestAccount::new([1; 32]).signer();
+        let mut account = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            IsSigner.late_validation(&signer.info(), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            IsSigner.late_validation(&account.info(), &mut context),
+            Err(ErrorCode::ConstraintSigner.into())
+        );
+    }
+}
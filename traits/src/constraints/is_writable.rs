@@ -0,0 +1,41 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct IsWritable;
+impl AccountInfoConstraint for IsWritable {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if account.is_writable() {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintMut.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn is_writable() {
+        let mut writable = TestAccount::new([1; 32]).writable();
+        let mut account = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            IsWritable.late_validation(&writable.info(), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            IsWritable.late_validation(&account.info(), &mut context),
+            Err(ErrorCode::ConstraintMut.into())
+        );
+    }
+}
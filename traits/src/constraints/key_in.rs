@@ -0,0 +1,42 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::pubkey::Pubkey;
+
+#[derive(Copy, Clone, Debug)]
+pub struct KeyIn<'a>(pub &'a [Pubkey]);
+impl AccountInfoConstraint for KeyIn<'_> {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if self.0.contains(account.key()) {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintAddress.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn key_in() {
+        let mut account = TestAccount::new([2; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            KeyIn(&[[1; 32], [2; 32]]).late_validation(&info, &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            KeyIn(&[[1; 32], [3; 32]]).late_validation(&info, &mut context),
+            Err(ErrorCode::ConstraintAddress.into())
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+#[derive(Copy, Clone, Debug)]
+pub struct MinLamports(pub u64);
+impl AccountInfoConstraint for MinLamports {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if account.lamports() >= self.0 {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintRaw.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn min_lamports() {
+        let mut account = TestAccount::new([1; 32]).lamports(100);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            MinLamports(100).late_validation(&info, &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            MinLamports(101).late_validation(&info, &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+    }
+}
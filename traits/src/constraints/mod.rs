@@ -1,3 +1,20 @@
 pub mod address;
+pub mod all;
+pub mod any;
+pub mod check;
+pub mod data_len;
+pub mod empty;
 pub mod executable;
+pub mod field_cmp;
+pub mod has_one;
+pub mod is_signer;
+pub mod is_writable;
+pub mod key_in;
+pub mod min_lamports;
+pub mod not;
+pub mod not_executable;
+pub mod owned_by;
 pub mod owner;
+pub mod rent_exempt;
+pub mod upgrade_authority;
+pub mod with_error;
@@ -0,0 +1,78 @@
+use crate::constraints::any::validate_fully;
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::program_error::ProgramError;
+
+/// Passes if `C` fails its early or late validation. Both are run during late validation, and
+/// the cleanup of `C` is not run.
+///
+/// Only constraint failures, errors with a custom code, are inverted. Builtin program errors such
+/// as a failed borrow or an unsupported sysvar are returned as is.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Not<C>(pub C);
+impl<C> AccountInfoConstraint for Not<C>
+where
+    C: AccountInfoConstraint,
+{
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        match validate_fully(&mut self.0, account, context) {
+            Ok(()) => Err(ErrorCode::ConstraintRaw.into()),
+            Err(error) if matches!(error.error, ProgramError::Custom(_)) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::check::Check;
+    use crate::constraints::is_signer::IsSigner;
+    use crate::test_util::{FailsEarly, TestAccount};
+
+    #[test]
+    fn inverts_early_failure() {
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            Not(FailsEarly).early_validation(&info, &mut context),
+            Ok(())
+        );
+        assert_eq!(Not(FailsEarly).late_validation(&info, &mut context), Ok(()));
+    }
+
+    #[test]
+    fn inverts_late_result() {
+        let mut signer = TestAccount::new([1; 32]).signer();
+        let mut other = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            Not(IsSigner).late_validation(&signer.info(), &mut context),
+            Err(ErrorCode::ConstraintRaw.into())
+        );
+        assert_eq!(
+            Not(IsSigner).late_validation(&other.info(), &mut context),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn propagates_program_errors() {
+        let mut account = TestAccount::new([1; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        let mut not = Not(Check::new(|_, _| {
+            Err(ProgramError::AccountBorrowFailed.into())
+        }));
+        assert_eq!(
+            not.late_validation(&account.info(), &mut context),
+            Err(ProgramError::AccountBorrowFailed.into())
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NotExecutable;
+impl AccountInfoConstraint for NotExecutable {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        if !account.executable() {
+            Ok(())
+        } else {
+            Err(ErrorCode::ConstraintExecutable.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn not_executable() {
+        let mut program = TestAccount::new([1; 32]).executable();
+        let mut account = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            NotExecutable.late_validation(&account.info(), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            NotExecutable.late_validation(&program.info(), &mut context),
+            Err(ErrorCode::ConstraintExecutable.into())
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::program::GetProgramId;
+use crate::traits::AccountsContext;
+use core::marker::PhantomData;
+use derive_where::derive_where;
+use pinocchio::account_info::AccountInfo;
+
+/// The account is owned by the program `P`.
+#[derive_where(Copy, Clone, Debug, Default)]
+pub struct OwnedBy<P>(PhantomData<fn() -> P>);
+impl<P> OwnedBy<P> {
+    #[inline]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+impl<P> AccountInfoConstraint for OwnedBy<P>
+where
+    P: GetProgramId,
+{
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        let program_id = P::program_id(context);
+        if account.owner() == program_id {
+            Ok(())
+        } else {
+            Err(AnchorError::from(ErrorCode::ConstraintOwner)
+                .with_pubkeys(*account.owner(), *program_id))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use crate::traits::program::CurrentProgram;
+
+    #[test]
+    fn owned_by_current_program() {
+        let mut owned = TestAccount::new([1; 32]).owner([2; 32]);
+        let mut other = TestAccount::new([1; 32]).owner([3; 32]);
+        let mut context = AccountsContext::new(&[2; 32]);
+        let mut constraint = OwnedBy::<CurrentProgram>::new();
+        assert_eq!(
+            constraint.late_validation(&owned.info(), &mut context),
+            Ok(())
+        );
+        assert_eq!(
+            constraint.late_validation(&other.info(), &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintOwner).with_pubkeys([3; 32], [2; 32]))
+        );
+    }
+}
@@ -1,23 +1,41 @@
-use crate::error::AnchorResult;
-use crate::traits::constraint::SupportsConstraint;
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
 use crate::traits::AccountsContext;
 use pinocchio::account_info::AccountInfo;
-use pinocchio::program_error::ProgramError;
 use pinocchio::pubkey::Pubkey;
 
 #[derive(Copy, Clone, Debug)]
 pub struct Owner(pub Pubkey);
 
-impl SupportsConstraint<Owner> for AccountInfo {
+impl AccountInfoConstraint for Owner {
     fn late_validation(
         &mut self,
-        constraint: &mut Owner,
+        account: &AccountInfo,
         _context: &mut AccountsContext,
     ) -> AnchorResult {
-        if self.key() == &constraint.0 {
+        if account.owner() == &self.0 {
             Ok(())
         } else {
-            Err(ProgramError::InvalidArgument)
+            Err(AnchorError::from(ErrorCode::ConstraintOwner)
+                .with_pubkeys(*account.owner(), self.0))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+
+    #[test]
+    fn owner() {
+        let mut account = TestAccount::new([1; 32]).owner([2; 32]);
+        let info = account.info();
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(Owner([2; 32]).late_validation(&info, &mut context), Ok(()));
+        assert_eq!(
+            Owner([3; 32]).late_validation(&info, &mut context),
+            Err(AnchorError::from(ErrorCode::ConstraintOwner).with_pubkeys([2; 32], [3; 32]))
+        );
+    }
+}
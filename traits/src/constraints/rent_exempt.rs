@@ -0,0 +1,65 @@
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::sysvars::rent::Rent;
+use pinocchio::sysvars::Sysvar;
+
+/// Checked against the rent sysvar.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RentExempt;
+impl AccountInfoConstraint for RentExempt {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        check_rent_exempt(&Rent::get()?, account)
+    }
+}
+
+fn check_rent_exempt(rent: &Rent, account: &AccountInfo) -> AnchorResult {
+    if rent.is_exempt(account.lamports(), account.data_len()) {
+        Ok(())
+    } else {
+        Err(ErrorCode::ConstraintRentExempt.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use pinocchio::program_error::ProgramError;
+
+    #[allow(deprecated)]
+    const RENT: Rent = Rent {
+        lamports_per_byte_year: 3480,
+        exemption_threshold: 2.0,
+        burn_percent: 50,
+    };
+
+    #[test]
+    fn rent_exempt() {
+        let minimum = RENT.minimum_balance(8);
+        let mut exempt = TestAccount::new([1; 32]).lamports(minimum).data(&[0; 8]);
+        let mut short = TestAccount::new([2; 32])
+            .lamports(minimum - 1)
+            .data(&[0; 8]);
+        assert_eq!(check_rent_exempt(&RENT, &exempt.info()), Ok(()));
+        assert_eq!(
+            check_rent_exempt(&RENT, &short.info()),
+            Err(ErrorCode::ConstraintRentExempt.into())
+        );
+    }
+
+    #[test]
+    fn rent_sysvar_unavailable_off_chain() {
+        let mut account = TestAccount::new([1; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        assert_eq!(
+            RentExempt.late_validation(&account.info(), &mut context),
+            Err(ProgramError::UnsupportedSysvar.into())
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::accounts::program_data::ProgramDataAccount;
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::pubkey::Pubkey;
+
+/// The account must be the signing upgrade authority of a program. Fails for immutable programs.
+#[derive(Copy, Clone, Debug)]
+pub struct UpgradeAuthority {
+    /// `None` if the program is immutable.
+    pub upgrade_authority: Option<Pubkey>,
+}
+impl UpgradeAuthority {
+    /// Fails if `program_data` has not been validated yet.
+    #[inline]
+    pub fn new<P, A, E>(program_data: &ProgramDataAccount<P, A, E>) -> AnchorResult<Self> {
+        Ok(Self {
+            upgrade_authority: program_data.upgrade_authority()?.copied(),
+        })
+    }
+}
+impl AccountInfoConstraint for UpgradeAuthority {
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        match &self.upgrade_authority {
+            None => Err(ErrorCode::ConstraintRaw.into()),
+            Some(authority) if account.key() != authority => {
+                Err(AnchorError::from(ErrorCode::ConstraintAddress)
+                    .with_pubkeys(*account.key(), *authority))
+            }
+            Some(_) if !account.is_signer() => Err(ErrorCode::ConstraintSigner.into()),
+            Some(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::error::{AnchorResult, CustomErrorGenerator};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
+
+/// Replaces the errors of `C` using the generator `E`, see [`AccountInfoConstraint::with_error`].
+#[derive(Copy, Clone, Debug)]
+pub struct WithError<C, E> {
+    pub constraint: C,
+    pub error: E,
+}
+impl<C, E> AccountInfoConstraint for WithError<C, E>
+where
+    C: AccountInfoConstraint,
+    E: CustomErrorGenerator + Clone,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        self.constraint
+            .early_validation(account, context)
+            .map_err(|error| self.error.clone().generate(error))
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        self.constraint
+            .late_validation(account, context)
+            .map_err(|error| self.error.clone().generate(error))
+    }
+
+    #[inline]
+    fn cleanup(&mut self, account: &AccountInfo, context: &mut AccountsContext) -> AnchorResult {
+        self.constraint
+            .cleanup(account, context)
+            .map_err(|error| self.error.clone().generate(error))
+    }
+}
@@ -0,0 +1,282 @@
+use crate::accounts::program::Program;
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, SingleAccount};
+use crate::traits::program::{GetProgramId, ProgramId};
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use crate::traits::AccountsContext;
+use crate::util::StackVec;
+use core::panic::Location;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::cpi::invoke_signed_with_bounds;
+use pinocchio::instruction::{AccountMeta, Instruction, Signer};
+use pinocchio::pubkey::Pubkey;
+
+/// Cross program invocation of `program_id` with the metas and infos of `accounts`.
+///
+/// `MAX_ACCOUNTS` on the invoke functions is the stack capacity for the accounts, at most
+/// [`pinocchio::cpi::MAX_CPI_ACCOUNTS`].
+#[derive(Debug)]
+pub struct Cpi<'a, A> {
+    pub program_id: &'a Pubkey,
+    pub accounts: &'a A,
+    pub data: &'a [u8],
+}
+impl<'a, A> Cpi<'a, A>
+where
+    A: Accounts,
+{
+    #[inline]
+    pub fn new(program_id: &'a Pubkey, accounts: &'a A, data: &'a [u8]) -> Self {
+        Self {
+            program_id,
+            accounts,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn from_program<P, T, E, const EXECUTABLE: bool>(
+        program: &'a Program<P, T, E, EXECUTABLE>,
+        accounts: &'a A,
+        data: &'a [u8],
+    ) -> Self
+    where
+        P: ProgramId,
+        T: SingleAccount,
+    {
+        Self::new(program.key(), accounts, data)
+    }
+
+    #[inline]
+    pub fn from_program_id<P>(
+        accounts_context: &AccountsContext<'a>,
+        accounts: &'a A,
+        data: &'a [u8],
+    ) -> Self
+    where
+        P: GetProgramId,
+    {
+        Self::new(P::program_id(accounts_context), accounts, data)
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn invoke<const MAX_ACCOUNTS: usize>(&self) -> AnchorResult {
+        self.invoke_signed_at::<MAX_ACCOUNTS>(&[], &[], Location::caller())
+    }
+
+    /// Signs for every [`Seeded`](crate::accounts::seeded::Seeded) account in `accounts` derived
+    /// from the current program, marking their metas as signers.
+    #[track_caller]
+    pub fn invoke_seeded<
+        const MAX_ACCOUNTS: usize,
+        const MAX_SEEDS: usize,
+        const MAX_SIGNERS: usize,
+    >(
+        &self,
+    ) -> AnchorResult
+    where
+        A: SignerSeeds,
+    {
+        let location = Location::caller();
+        let signers = self.signer_seeds::<MAX_SEEDS, MAX_SIGNERS>()?;
+        signers.with_signers(|signer_seeds| {
+            self.invoke_signed_at::<MAX_ACCOUNTS>(signer_seeds, signers.signer_keys(), location)
+        })
+    }
+
+    fn signer_seeds<const MAX_SEEDS: usize, const MAX_SIGNERS: usize>(
+        &self,
+    ) -> AnchorResult<SignerSeedsBuffer<'a, MAX_SEEDS, MAX_SIGNERS>>
+    where
+        A: SignerSeeds,
+    {
+        let mut signers = SignerSeedsBuffer::new();
+        self.accounts.signer_seeds(&mut signers)?;
+        Ok(signers)
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn invoke_signed<const MAX_ACCOUNTS: usize>(&self, signers: &[Signer]) -> AnchorResult {
+        self.invoke_signed_at::<MAX_ACCOUNTS>(signers, &[], Location::caller())
+    }
+
+    fn invoke_signed_at<const MAX_ACCOUNTS: usize>(
+        &self,
+        signers: &[Signer],
+        signer_keys: &[Pubkey],
+        location: &Location<'_>,
+    ) -> AnchorResult {
+        let (metas, infos) = self.metas_and_infos::<MAX_ACCOUNTS>(signer_keys, location)?;
+        let mut info_refs = StackVec::<&AccountInfo, MAX_ACCOUNTS>::new();
+        for info in infos.iter() {
+            // Cannot fail, `infos` has the same capacity.
+            let _ = info_refs.push(info);
+        }
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(
+            &Instruction {
+                program_id: self.program_id,
+                data: self.data,
+                accounts: &metas,
+            },
+            &info_refs,
+            signers,
+        )
+        .map_err(AnchorError::from)
+    }
+
+    /// The metas and matching infos of `accounts`, with the metas of `signer_keys` marked as
+    /// signers.
+    #[allow(clippy::type_complexity)]
+    fn metas_and_infos<const MAX_ACCOUNTS: usize>(
+        &self,
+        signer_keys: &[Pubkey],
+        location: &Location<'_>,
+    ) -> AnchorResult<(
+        StackVec<AccountMeta<'a>, MAX_ACCOUNTS>,
+        StackVec<AccountInfo, MAX_ACCOUNTS>,
+    )> {
+        let capacity_exceeded =
+            || AnchorError::from(ErrorCode::AccountsCapacityExceeded).logged(location, None);
+
+        let mut metas = StackVec::<AccountMeta, MAX_ACCOUNTS>::new();
+        for mut meta in self.accounts.to_account_metas(None) {
+            meta.is_signer |= signer_keys.contains(meta.pubkey);
+            metas.push(meta).map_err(|_| capacity_exceeded())?;
+        }
+
+        let mut infos = StackVec::<AccountInfo, MAX_ACCOUNTS>::new();
+        for info in self.accounts.to_account_infos() {
+            infos.push(info).map_err(|_| capacity_exceeded())?;
+        }
+
+        if metas.len() != infos.len() {
+            return Err(AnchorError::from(ErrorCode::AccountNotEnoughKeys).logged(location, None));
+        }
+        for (meta, info) in metas.iter().zip(infos.iter()) {
+            if meta.pubkey != info.key() {
+                return Err(AnchorError::from(ErrorCode::ConstraintAddress)
+                    .with_pubkeys(*meta.pubkey, *info.key())
+                    .logged(location, Some(info.key())));
+            }
+        }
+
+        Ok((metas, infos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::seeded::{Seeded, SeedsWithBump};
+    use crate::test_util::TestAccount;
+    use core::marker::PhantomData;
+
+    const PROGRAM: Pubkey = [7; 32];
+
+    /// Metas of `metas` but infos of `infos`.
+    struct Mismatched<'a> {
+        metas: &'a [Pubkey],
+        infos: &'a [AccountInfo],
+    }
+    impl Accounts for Mismatched<'_> {
+        fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+            self.infos.iter().copied()
+        }
+
+        fn to_account_metas(
+            &self,
+            _is_signer: Option<bool>,
+        ) -> impl Iterator<Item = AccountMeta<'_>> {
+            self.metas.iter().map(AccountMeta::readonly)
+        }
+    }
+
+    #[test]
+    fn invokes_with_matching_accounts() {
+        let [mut a, mut b] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let accounts = [a.info(), b.info()];
+        let cpi = Cpi::new(&PROGRAM, &accounts, &[1, 2, 3]);
+
+        let (metas, infos) = cpi.metas_and_infos::<2>(&[], Location::caller()).unwrap();
+        assert_eq!(metas.len(), 2);
+        assert_eq!(metas[1].pubkey, &[2; 32]);
+        assert!(!metas[1].is_signer);
+        assert_eq!(infos[1].key(), &[2; 32]);
+        assert_eq!(cpi.invoke::<2>(), Ok(()));
+    }
+
+    #[test]
+    fn fails_over_capacity() {
+        let [mut a, mut b, mut c] = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let accounts = [a.info(), b.info(), c.info()];
+        assert_eq!(
+            Cpi::new(&PROGRAM, &accounts, &[])
+                .invoke::<2>()
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountsCapacityExceeded.into())
+        );
+    }
+
+    #[test]
+    fn fails_with_fewer_infos_than_metas() {
+        let mut a = TestAccount::new([1; 32]);
+        let accounts = Mismatched {
+            metas: &[[1; 32], [2; 32]],
+            infos: &[a.info()],
+        };
+        assert_eq!(
+            Cpi::new(&PROGRAM, &accounts, &[])
+                .invoke::<4>()
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountNotEnoughKeys.into())
+        );
+    }
+
+    #[test]
+    fn fails_with_mismatched_keys() {
+        let [mut a, mut b] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let accounts = Mismatched {
+            metas: &[[1; 32], [3; 32]],
+            infos: &[a.info(), b.info()],
+        };
+        assert_eq!(
+            Cpi::new(&PROGRAM, &accounts, &[]).invoke::<4>(),
+            Err(AnchorError::from(ErrorCode::ConstraintAddress).with_pubkeys([3; 32], [2; 32]))
+        );
+    }
+
+    #[test]
+    fn seeded_accounts_sign() {
+        let [mut pda, mut other] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let seeded = |info, seeds| Seeded::<AccountInfo, ([u8; 4],)> {
+            account: info,
+            seeds,
+            _phantom_program: PhantomData,
+            _phantom_error: PhantomData,
+        };
+        let accounts = [
+            seeded(
+                pda.info(),
+                Some(SeedsWithBump {
+                    seeds: (*b"seed",),
+                    bump: 254,
+                }),
+            ),
+            seeded(other.info(), None),
+        ];
+        let cpi = Cpi::new(&PROGRAM, &accounts, &[]);
+
+        let signers = cpi.signer_seeds::<2, 2>().unwrap();
+        assert_eq!(signers.signer_keys(), [[1; 32]]);
+        let (metas, _) = cpi
+            .metas_and_infos::<2>(signers.signer_keys(), Location::caller())
+            .unwrap();
+        assert!(metas[0].is_signer);
+        assert!(!metas[1].is_signer);
+
+        assert_eq!(cpi.invoke_seeded::<2, 2, 2>(), Ok(()));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::{AnchorError, AnchorResult};
+use alloc::vec::Vec;
+use core::panic::Location;
+use pinocchio::pubkey::Pubkey;
+
+/// A validation failure recorded in collect-all mode.
+#[derive(Copy, Clone, Debug)]
+pub struct Diagnostic {
+    /// Index of the account in the instruction's accounts, if it was decoded.
+    pub account_index: Option<usize>,
+    pub key: Option<Pubkey>,
+    /// Type name of the wrapper that reported the failure.
+    pub wrapper: &'static str,
+    pub error: AnchorError,
+    pub location: &'static Location<'static>,
+}
+
+/// Collects every validation failure instead of failing on the first one.
+///
+/// Enabled within [`AccountsContext::collect_all`](crate::traits::AccountsContext::collect_all),
+/// which still fails with the first failure. Validation keeps going past failures reported
+/// through [`AccountsContext::report`](crate::traits::AccountsContext::report). Decode failures,
+/// like the writability check of [`Mutability`](crate::accounts::mutable::Mutability) or
+/// deserializing a data account, return immediately and end collection, as later accounts can't
+/// be decoded.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// Keys of decoded accounts in order, used to resolve [`Diagnostic::account_index`].
+    pub decoded_keys: Vec<Pubkey>,
+    pub failures: Vec<Diagnostic>,
+}
+impl Diagnostics {
+    /// Returns the first failure, if any.
+    pub fn result(&self) -> AnchorResult {
+        match self.failures.first() {
+            Some(diagnostic) => Err(diagnostic.error),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "log-errors")]
+    pub fn log(&self) {
+        pinocchio::log::sol_log(&alloc::format!(
+            "{} account validation failure(s)",
+            self.failures.len()
+        ));
+        for diagnostic in &self.failures {
+            if let Some(index) = diagnostic.account_index {
+                pinocchio::log::sol_log(&alloc::format!(
+                    "Account index {index}: {}",
+                    diagnostic.wrapper
+                ));
+            } else {
+                pinocchio::log::sol_log(diagnostic.wrapper);
+            }
+            diagnostic
+                .error
+                .log(diagnostic.location, diagnostic.key.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accounts::mutable::ReadOnly;
+    use crate::accounts::signer::Signer;
+    use crate::error::ErrorCode;
+    use crate::test_util::TestAccount;
+    use crate::traits::account::{DecodeAccounts, ValidateAccounts};
+    use crate::traits::AccountsContext;
+    use pinocchio::program_error::ProgramError;
+
+    #[test]
+    fn collects_every_failure_and_returns_the_first() {
+        let mut first = TestAccount::new([1; 32]);
+        let mut second = TestAccount::new([2; 32]);
+        let mut context = AccountsContext::new(&[0; 32]);
+        let infos = [first.info(), second.info()];
+
+        let (result, diagnostics) = context.collect_all(|context| {
+            let mut accounts = <[Signer; 2]>::try_accounts(context, &mut infos.into_iter(), ())?;
+            accounts.validate(context, ())
+        });
+        assert_eq!(
+            result.map_err(|error| error.error),
+            Err(ErrorCode::AccountNotSigner.into())
+        );
+        assert_eq!(diagnostics.failures.len(), 2);
+        assert_eq!(diagnostics.failures[0].account_index, Some(0));
+        assert_eq!(diagnostics.failures[1].key, Some([2; 32]));
+        assert!(context.diagnostics.is_none());
+    }
+
+    #[test]
+    fn decode_failures_end_collection() {
+        let mut signer = TestAccount::new([1; 32]);
+        let mut writable = TestAccount::new([2; 32]).writable();
+        let mut context = AccountsContext::new(&[0; 32]);
+        let infos = [signer.info(), writable.info()];
+
+        let (result, diagnostics) = context.collect_all(|context| {
+            let accounts = &mut infos.into_iter();
+            <Signer>::try_accounts(context, accounts, ())?.validate(context, ())?;
+            <ReadOnly>::try_accounts(context, accounts, ())?;
+            Ok(())
+        });
+        assert_eq!(
+            result.map_err(|error| error.error),
+            Err(ErrorCode::AccountNotSigner.into())
+        );
+        assert_eq!(diagnostics.failures.len(), 1);
+
+        let (result, diagnostics) = context.collect_all(|context| {
+            <ReadOnly>::try_accounts(context, &mut [writable.info()].into_iter(), ()).map(drop)
+        });
+        assert_eq!(
+            result.map_err(|error| error.error),
+            Err(ProgramError::from(ErrorCode::ConstraintMut))
+        );
+        assert!(diagnostics.failures.is_empty());
+    }
+}
@@ -1,8 +1,263 @@
+use core::fmt::{Display, Formatter};
+use core::panic::Location;
 use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::Pubkey;
 
-pub type AnchorError = ProgramError;
 pub type AnchorResult<T = ()> = Result<T, AnchorError>;
 
+/// Custom program errors below this are reserved for [`ErrorCode`].
+pub const ERROR_CODE_OFFSET: u32 = 6000;
+/// Custom program errors from this are reserved for this crate's own [`ErrorCode`]s, far above
+/// Anchor's codes and those of programs.
+pub const CRATE_ERROR_CODE_OFFSET: u32 = 0xFFFF_0000;
+
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $name:ident = $code:literal => $message:literal,)+) => {
+        /// Error codes reserved by Anchor, and by this crate from [`CRATE_ERROR_CODE_OFFSET`],
+        /// returned as [`ProgramError::Custom`].
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        #[repr(u32)]
+        pub enum ErrorCode {
+            $($(#[$meta])* $name = $code,)+
+        }
+        impl ErrorCode {
+            pub const fn from_u32(code: u32) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$name),)+
+                    _ => None,
+                }
+            }
+
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($name),)+
+                }
+            }
+
+            pub const fn message(self) -> &'static str {
+                match self {
+                    $(Self::$name => $message,)+
+                }
+            }
+        }
+    };
+}
+error_codes! {
+    InstructionMissing = 100 => "8 byte instruction identifier not provided",
+    InstructionFallbackNotFound = 101 => "Fallback functions are not supported",
+    InstructionDidNotDeserialize = 102 => "The program could not deserialize the given instruction",
+    InstructionDidNotSerialize = 103 => "The program could not serialize the given instruction",
+
+    ConstraintMut = 2000 => "A mut constraint was violated",
+    ConstraintHasOne = 2001 => "A has one constraint was violated",
+    ConstraintSigner = 2002 => "A signer constraint was violated",
+    ConstraintRaw = 2003 => "A raw constraint was violated",
+    ConstraintOwner = 2004 => "An owner constraint was violated",
+    ConstraintRentExempt = 2005 => "A rent exemption constraint was violated",
+    ConstraintSeeds = 2006 => "A seeds constraint was violated",
+    ConstraintExecutable = 2007 => "An executable constraint was violated",
+    ConstraintState = 2008 => "Deprecated Error, feel free to replace with something else",
+    ConstraintAssociated = 2009 => "An associated constraint was violated",
+    ConstraintAssociatedInit = 2010 => "An associated init constraint was violated",
+    ConstraintClose = 2011 => "A close constraint was violated",
+    ConstraintAddress = 2012 => "An address constraint was violated",
+    ConstraintZero = 2013 => "Expected zero account discriminant",
+    ConstraintTokenMint = 2014 => "A token mint constraint was violated",
+    ConstraintTokenOwner = 2015 => "A token owner constraint was violated",
+    ConstraintMintMintAuthority = 2016 => "A mint mint authority constraint was violated",
+    ConstraintMintFreezeAuthority = 2017 => "A mint freeze authority constraint was violated",
+    ConstraintMintDecimals = 2018 => "A mint decimals constraint was violated",
+    ConstraintSpace = 2019 => "A space constraint was violated",
+    ConstraintAccountIsNone = 2020 => "A required account for the constraint is None",
+
+    RequireViolated = 2500 => "A require expression was violated",
+    RequireEqViolated = 2501 => "A require_eq expression was violated",
+    RequireKeysEqViolated = 2502 => "A require_keys_eq expression was violated",
+    RequireNeqViolated = 2503 => "A require_neq expression was violated",
+    RequireKeysNeqViolated = 2504 => "A require_keys_neq expression was violated",
+    RequireGtViolated = 2505 => "A require_gt expression was violated",
+    RequireGteViolated = 2506 => "A require_gte expression was violated",
+
+    AccountDiscriminatorAlreadySet = 3000 => "The account discriminator was already set on this account",
+    AccountDiscriminatorNotFound = 3001 => "No discriminator was found on the account",
+    AccountDiscriminatorMismatch = 3002 => "Account discriminator did not match what was expected",
+    AccountDidNotDeserialize = 3003 => "Failed to deserialize the account",
+    AccountDidNotSerialize = 3004 => "Failed to serialize the account",
+    AccountNotEnoughKeys = 3005 => "Not enough account keys given to the instruction",
+    AccountNotMutable = 3006 => "The given account is not mutable",
+    AccountOwnedByWrongProgram = 3007 => "The given account is owned by a different program than expected",
+    InvalidProgramId = 3008 => "Program ID was not as expected",
+    InvalidProgramExecutable = 3009 => "Program account is not executable",
+    AccountNotSigner = 3010 => "The given account did not sign",
+    AccountNotSystemOwned = 3011 => "The given account is not owned by the system program",
+    AccountNotInitialized = 3012 => "The program expected this account to be already initialized",
+    AccountNotProgramData = 3013 => "The given account is not a program data account",
+    AccountNotAssociatedTokenAccount = 3014 => "The given account is not the associated token account",
+    AccountSysvarMismatch = 3015 => "The given public key does not match the required sysvar",
+    AccountReallocExceedsLimit = 3016 => "The account reallocation exceeds the MAX_PERMITTED_DATA_INCREASE limit",
+    AccountDuplicateReallocs = 3017 => "The account was duplicated for more than one reallocation",
+
+    DeclaredProgramIdMismatch = 4100 => "The declared program id does not match the actual program id",
+    TryingToInitPayerAsProgramAccount = 4101 => "You cannot/should not initialize the payer account as a program account",
+    InvalidNumericConversion = 4102 => "The program could not perform the numeric conversion, out of range integral type conversion attempted",
+
+    Deprecated = 5000 => "The API being used is deprecated and should no longer be used",
+
+    /// Not an Anchor code, returned by fixed capacity account buffers.
+    AccountsCapacityExceeded = 0xFFFF_0000 => "More accounts were given than the buffer can hold",
+    /// Not an Anchor code, the rest are returned for `ReturnDataError`s.
+    ReturnDataMissing = 0xFFFF_0001 => "No return data was set",
+    ReturnDataTooLarge = 0xFFFF_0002 => "The return data is larger than the maximum",
+    ReturnDataDidNotSerialize = 0xFFFF_0003 => "Failed to serialize the return data",
+    ReturnDataDidNotDeserialize = 0xFFFF_0004 => "Failed to deserialize the return data",
+}
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+impl From<ErrorCode> for ProgramError {
+    fn from(value: ErrorCode) -> Self {
+        ProgramError::Custom(value as u32)
+    }
+}
+
+/// An error with the account it occurred on, if known.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AnchorError {
+    /// [`ProgramError::Custom`] for [`ErrorCode`]s.
+    pub error: ProgramError,
+    pub account_name: Option<&'static str>,
+    /// The left and right values of a failed comparison, e.g. the found and expected owner.
+    pub compared_pubkeys: Option<(Pubkey, Pubkey)>,
+}
+impl AnchorError {
+    #[inline]
+    pub const fn new(error: ProgramError) -> Self {
+        Self {
+            error,
+            account_name: None,
+            compared_pubkeys: None,
+        }
+    }
+
+    #[inline]
+    pub const fn with_account_name(mut self, account_name: &'static str) -> Self {
+        self.account_name = Some(account_name);
+        self
+    }
+
+    #[inline]
+    pub const fn with_pubkeys(mut self, left: Pubkey, right: Pubkey) -> Self {
+        self.compared_pubkeys = Some((left, right));
+        self
+    }
+
+    /// The [`ErrorCode`] of this error if it is one.
+    pub const fn error_code(&self) -> Option<ErrorCode> {
+        match self.error {
+            ProgramError::Custom(code) => ErrorCode::from_u32(code),
+            _ => None,
+        }
+    }
+
+    /// Logs this error with the `log-errors` feature, see [`AnchorError::log`].
+    #[inline]
+    pub fn logged(self, location: &Location<'_>, key: Option<&Pubkey>) -> Self {
+        #[cfg(feature = "log-errors")]
+        self.log(location, key);
+        #[cfg(not(feature = "log-errors"))]
+        let _ = (location, key);
+        self
+    }
+
+    /// Logs this error in the format of Anchor so existing client log parsers understand it.
+    /// `location` is where the failing lifecycle method was called and `key` the account it
+    /// failed on.
+    #[cfg(feature = "log-errors")]
+    pub fn log(&self, location: &Location<'_>, key: Option<&Pubkey>) {
+        use alloc::format;
+        use pinocchio::log::sol_log;
+
+        let (kind, details) = match self.error_code() {
+            Some(code) => (
+                "AnchorError",
+                format!(
+                    "Error Code: {}. Error Number: {}. Error Message: {}.",
+                    code.name(),
+                    code as u32,
+                    code.message()
+                ),
+            ),
+            None => (
+                "ProgramError",
+                format!(
+                    "Error Code: {:?}. Error Number: {}.",
+                    self.error,
+                    u64::from(self.error)
+                ),
+            ),
+        };
+        match self.account_name {
+            Some(account_name) => {
+                sol_log(&format!(
+                    "{kind} caused by account: {account_name}. {details}"
+                ));
+                sol_log(&format!(
+                    "Location: {}:{}.",
+                    location.file(),
+                    location.line()
+                ));
+            }
+            None => sol_log(&format!(
+                "{kind} thrown in {}:{}. {details}",
+                location.file(),
+                location.line()
+            )),
+        }
+        if let Some(key) = key {
+            sol_log("Account:");
+            pinocchio::pubkey::log(key);
+        }
+        if let Some((left, right)) = &self.compared_pubkeys {
+            sol_log("Left:");
+            pinocchio::pubkey::log(left);
+            sol_log("Right:");
+            pinocchio::pubkey::log(right);
+        }
+    }
+}
+impl Display for AnchorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.error_code() {
+            Some(code) => Display::fmt(&code, f)?,
+            None => write!(f, "{:?}", self.error)?,
+        }
+        if let Some(account_name) = self.account_name {
+            write!(f, " (account: {account_name})")?;
+        }
+        Ok(())
+    }
+}
+impl From<ErrorCode> for AnchorError {
+    #[inline]
+    fn from(value: ErrorCode) -> Self {
+        Self::new(value.into())
+    }
+}
+impl From<ProgramError> for AnchorError {
+    #[inline]
+    fn from(value: ProgramError) -> Self {
+        Self::new(value)
+    }
+}
+impl From<AnchorError> for ProgramError {
+    #[inline]
+    fn from(value: AnchorError) -> Self {
+        value.error
+    }
+}
+
 pub trait CustomErrorGenerator {
     fn generate(self, proposed_error: AnchorError) -> AnchorError;
 }
@@ -19,6 +274,23 @@ impl CustomErrorGenerator for AnchorError {
     }
 }
 
+/// Replaces the error, keeping the account and compared values.
+impl CustomErrorGenerator for ProgramError {
+    fn generate(self, proposed_error: AnchorError) -> AnchorError {
+        AnchorError {
+            error: self,
+            ..proposed_error
+        }
+    }
+}
+
+/// Replaces the error, keeping the account and compared values.
+impl CustomErrorGenerator for ErrorCode {
+    fn generate(self, proposed_error: AnchorError) -> AnchorError {
+        ProgramError::from(self).generate(proposed_error)
+    }
+}
+
 impl<F> CustomErrorGenerator for F
 where
     F: FnOnce(AnchorError) -> AnchorError,
@@ -27,3 +299,33 @@ where
         self(proposed_error)
     }
 }
+
+/// Replaces the error with [`ProgramError::Custom`] of `CODE`. Usable as the type level generator
+/// of wrappers, e.g. `Signer<AccountInfo, CustomError<6000>>`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct CustomError<const CODE: u32>;
+impl<const CODE: u32> CustomErrorGenerator for CustomError<CODE> {
+    fn generate(self, proposed_error: AnchorError) -> AnchorError {
+        ProgramError::Custom(CODE).generate(proposed_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_codes_are_outside_anchor_and_program_ranges() {
+        let code = ErrorCode::AccountsCapacityExceeded as u32;
+        assert!(code >= CRATE_ERROR_CODE_OFFSET);
+        assert_eq!(
+            ErrorCode::from_u32(code),
+            Some(ErrorCode::AccountsCapacityExceeded)
+        );
+        assert_eq!(
+            AnchorError::from(ErrorCode::AccountsCapacityExceeded).error_code(),
+            Some(ErrorCode::AccountsCapacityExceeded)
+        );
+        assert_eq!(ErrorCode::from_u32(3100), None);
+    }
+}
@@ -1,21 +1,22 @@
-use crate::error::AnchorResult;
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
 use crate::traits::maybe_bool::Unknown;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use core::iter::once;
+use core::panic::Location;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
-use pinocchio::program_error::ProgramError;
 
 impl Accounts for AccountInfo {
     #[inline]
     fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
         once(AccountMeta::new(
             self.key(),
-            is_signer.unwrap_or_else(|| self.is_signer()),
             self.is_writable(),
+            is_signer.unwrap_or_else(|| self.is_signer()),
         ))
     }
 
@@ -35,11 +36,26 @@ unsafe impl SingleAccount for AccountInfo {
 }
 impl DecodeAccounts<()> for AccountInfo {
     fn try_accounts(
-        _accounts_context: &mut AccountsContext,
+        accounts_context: &mut AccountsContext,
         accounts: &mut impl Iterator<Item = AccountInfo>,
         _arg: (),
     ) -> AnchorResult<Self> {
-        accounts.next().ok_or(ProgramError::NotEnoughAccountKeys)
+        let location = Location::caller();
+        let account = accounts.next().ok_or_else(|| {
+            AnchorError::from(ErrorCode::AccountNotEnoughKeys).logged(location, None)
+        })?;
+        #[cfg(feature = "alloc")]
+        if let Some(diagnostics) = &mut accounts_context.diagnostics {
+            diagnostics.decoded_keys.push(*account.key());
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = accounts_context;
+        Ok(account)
+    }
+
+    #[inline]
+    fn size_hint() -> (usize, Option<usize>) {
+        (1, Some(1))
     }
 }
 impl ValidateAccounts<()> for AccountInfo {
@@ -52,3 +68,35 @@ impl CleanupAccounts<()> for AccountInfo {
         Ok(())
     }
 }
+impl SignerSeeds for AccountInfo {
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        _buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::TestAccount;
+    use crate::traits::account::Accounts;
+
+    #[test]
+    fn account_metas_flags() {
+        let mut writable = TestAccount::new([1; 32]).writable();
+        let info = writable.info();
+        let meta = info.to_account_metas(None).next().unwrap();
+        assert!(meta.is_writable);
+        assert!(!meta.is_signer);
+
+        let mut signer = TestAccount::new([2; 32]).signer();
+        let info = signer.info();
+        let meta = info.to_account_metas(None).next().unwrap();
+        assert!(!meta.is_writable);
+        assert!(meta.is_signer);
+        let meta = info.to_account_metas(Some(false)).next().unwrap();
+        assert!(!meta.is_signer);
+    }
+}
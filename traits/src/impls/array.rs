@@ -2,6 +2,8 @@ use crate::error::AnchorResult;
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use crate::util::try_map_array_init;
 use array_init::try_array_init;
@@ -160,3 +162,50 @@ where
         Self::cleanup(self, accounts_context, (arg,))
     }
 }
+impl<T, const N: usize> SignerSeeds for [T; N]
+where
+    T: SignerSeeds,
+{
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        for t in self {
+            T::signer_seeds(t, buffer)?;
+        }
+        Ok(())
+    }
+}
+impl<T, const N: usize, C> SupportsConstraint<C> for [T; N]
+where
+    T: SupportsConstraint<C>,
+{
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::early_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::late_validation(t, constraint, context)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        for t in self.iter_mut() {
+            T::cleanup(t, constraint, context)?;
+        }
+        Ok(())
+    }
+}
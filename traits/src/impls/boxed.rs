@@ -2,6 +2,8 @@ use crate::error::AnchorResult;
 use crate::traits::account::{
     Accounts, CleanupAccounts, DecodeAccounts, SingleAccount, ValidateAccounts,
 };
+use crate::traits::constraint::SupportsConstraint;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
 use crate::traits::AccountsContext;
 use alloc::boxed::Box;
 use pinocchio::account_info::AccountInfo;
@@ -67,3 +69,42 @@ where
         T::cleanup(self, accounts_context, arg)
     }
 }
+impl<T> SignerSeeds for Box<T>
+where
+    T: SignerSeeds,
+{
+    #[inline]
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult {
+        T::signer_seeds(self, buffer)
+    }
+}
+impl<T, C> SupportsConstraint<C> for Box<T>
+where
+    T: SupportsConstraint<C>,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        T::early_validation(self, constraint, context)
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        T::late_validation(self, constraint, context)
+    }
+
+    #[inline]
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        T::cleanup(self, constraint, context)
+    }
+}
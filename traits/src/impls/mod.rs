@@ -1,3 +1,4 @@
 mod account_info;
 pub mod array;
+#[cfg(feature = "alloc")]
 pub mod boxed;
@@ -1,11 +1,21 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 pub mod accounts;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod constraints;
+pub mod cpi;
+#[cfg(feature = "alloc")]
+pub mod diagnostics;
 pub mod error;
 pub mod impls;
 pub mod programs;
+#[cfg(feature = "borsh")]
+pub mod return_data;
+#[cfg(test)]
+mod test_util;
 pub mod traits;
 mod util;
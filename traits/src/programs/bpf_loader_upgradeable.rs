@@ -0,0 +1,70 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::maybe_bool::False;
+use crate::traits::program::ProgramId;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::pubkey::Pubkey;
+
+/// `BPFLoaderUpgradeab1e11111111111111111111111`
+pub struct BpfLoaderUpgradeable;
+impl ProgramId for BpfLoaderUpgradeable {
+    const ID: Pubkey = [
+        2, 168, 246, 145, 78, 136, 161, 176, 226, 16, 21, 62, 247, 99, 174, 43, 0, 194, 185, 61,
+        22, 193, 36, 210, 192, 83, 122, 16, 4, 128, 0, 0,
+    ];
+
+    type IsCurrentProgram = False;
+}
+
+/// `UpgradeableLoaderState::Program` tag.
+const PROGRAM_TAG: u32 = 2;
+/// `UpgradeableLoaderState::ProgramData` tag.
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// The address of the program data account stored in an upgradeable program account.
+pub fn program_data_address(program: &AccountInfo) -> AnchorResult<Pubkey> {
+    if program.owner() != &BpfLoaderUpgradeable::ID {
+        return Err(AnchorError::from(ErrorCode::InvalidProgramId)
+            .with_pubkeys(*program.owner(), BpfLoaderUpgradeable::ID));
+    }
+    match program.try_borrow_data()?.split_first_chunk::<4>() {
+        Some((tag, data)) if *tag == PROGRAM_TAG.to_le_bytes() => data
+            .first_chunk::<32>()
+            .copied()
+            .ok_or_else(|| ErrorCode::InvalidProgramId.into()),
+        _ => Err(ErrorCode::InvalidProgramId.into()),
+    }
+}
+
+/// The metadata at the start of a program data account.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ProgramDataHeader {
+    /// Slot the program was last deployed at.
+    pub slot: u64,
+    /// `None` if the program is immutable.
+    pub upgrade_authority: Option<Pubkey>,
+}
+impl ProgramDataHeader {
+    /// Length of the header, the program's ELF follows it.
+    pub const LEN: usize = 4 + 8 + 1 + 32;
+
+    /// Parses the header of a program data account owned by the loader.
+    pub fn parse(program_data: &AccountInfo) -> AnchorResult<Self> {
+        if program_data.owner() != &BpfLoaderUpgradeable::ID {
+            return Err(AnchorError::from(ErrorCode::AccountNotProgramData)
+                .with_pubkeys(*program_data.owner(), BpfLoaderUpgradeable::ID));
+        }
+        Self::from_bytes(&program_data.try_borrow_data()?)
+            .ok_or_else(|| ErrorCode::AccountNotProgramData.into())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let (tag, data) = data.split_first_chunk::<4>()?;
+        let (slot, data) = data.split_first_chunk::<8>()?;
+        let (option, authority) = data.split_first()?;
+        let authority = authority.first_chunk::<32>()?;
+        (*tag == PROGRAM_DATA_TAG.to_le_bytes()).then(|| Self {
+            slot: u64::from_le_bytes(*slot),
+            upgrade_authority: (*option != 0).then_some(*authority),
+        })
+    }
+}
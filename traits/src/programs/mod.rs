@@ -1 +1,2 @@
+pub mod bpf_loader_upgradeable;
 pub mod system;
@@ -1,6 +1,14 @@
+use crate::accounts::program::Program;
+use crate::error::{AnchorError, AnchorResult};
+use crate::traits::account::{SigningAccount, SingleAccount, WritableAccount};
 use crate::traits::maybe_bool::False;
 use crate::traits::program::ProgramId;
-use pinocchio::pubkey::Pubkey;
+use crate::traits::signer_seeds::{SignerSeeds, SignerSeedsBuffer};
+use pinocchio::account_info::AccountInfo;
+use pinocchio::cpi::invoke_signed;
+use pinocchio::instruction::{AccountMeta, Instruction, Signer};
+use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
 
 pub struct System;
 impl ProgramId for System {
@@ -8,3 +16,808 @@ impl ProgramId for System {
 
     type IsCurrentProgram = False;
 }
+
+/// System instructions have at most two signers.
+type SystemSigners<'a> = SignerSeedsBuffer<'a, { 2 * MAX_SEEDS }, 2>;
+
+impl<T, E, const EXECUTABLE: bool> Program<System, T, E, EXECUTABLE> {
+    pub fn transfer(
+        &self,
+        from: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        to: &impl WritableAccount,
+        lamports: u64,
+    ) -> AnchorResult {
+        from.check_writable()?;
+        from.check_signer()?;
+        to.check_writable()?;
+        let mut signers = SystemSigners::new();
+        from.signer_seeds(&mut signers)?;
+        let instruction =
+            transfer_instruction(from.account_info_ref(), to.account_info_ref(), lamports);
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn create_account(
+        &self,
+        from: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        to: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        lamports: u64,
+        space: u64,
+        owner: &Pubkey,
+    ) -> AnchorResult {
+        from.check_writable()?;
+        from.check_signer()?;
+        to.check_writable()?;
+        to.check_signer()?;
+        let mut signers = SystemSigners::new();
+        from.signer_seeds(&mut signers)?;
+        to.signer_seeds(&mut signers)?;
+        let instruction = create_account_instruction(
+            from.account_info_ref(),
+            to.account_info_ref(),
+            lamports,
+            space,
+            owner,
+        );
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    /// `base` may be the same account as `from`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_account_with_seed(
+        &self,
+        from: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        to: &impl WritableAccount,
+        base: &(impl SigningAccount + SignerSeeds),
+        seed: &str,
+        lamports: u64,
+        space: u64,
+        owner: &Pubkey,
+    ) -> AnchorResult {
+        from.check_writable()?;
+        from.check_signer()?;
+        to.check_writable()?;
+        base.check_signer()?;
+        let mut signers = SystemSigners::new();
+        from.signer_seeds(&mut signers)?;
+        if base.key() != from.key() {
+            base.signer_seeds(&mut signers)?;
+        }
+        let instruction = create_account_with_seed_instruction(
+            from.account_info_ref(),
+            to.account_info_ref(),
+            base.account_info_ref(),
+            seed,
+            lamports,
+            space,
+            owner,
+        )?;
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn allocate(
+        &self,
+        account: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        space: u64,
+    ) -> AnchorResult {
+        account.check_writable()?;
+        account.check_signer()?;
+        let mut signers = SystemSigners::new();
+        account.signer_seeds(&mut signers)?;
+        let instruction = allocate_instruction(account.account_info_ref(), space);
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn allocate_with_seed(
+        &self,
+        account: &impl WritableAccount,
+        base: &(impl SigningAccount + SignerSeeds),
+        seed: &str,
+        space: u64,
+        owner: &Pubkey,
+    ) -> AnchorResult {
+        account.check_writable()?;
+        base.check_signer()?;
+        let mut signers = SystemSigners::new();
+        base.signer_seeds(&mut signers)?;
+        let instruction = allocate_with_seed_instruction(
+            account.account_info_ref(),
+            base.account_info_ref(),
+            seed,
+            space,
+            owner,
+        )?;
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn assign(
+        &self,
+        account: &(impl WritableAccount + SigningAccount + SignerSeeds),
+        owner: &Pubkey,
+    ) -> AnchorResult {
+        account.check_writable()?;
+        account.check_signer()?;
+        let mut signers = SystemSigners::new();
+        account.signer_seeds(&mut signers)?;
+        let instruction = assign_instruction(account.account_info_ref(), owner);
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn assign_with_seed(
+        &self,
+        account: &impl WritableAccount,
+        base: &(impl SigningAccount + SignerSeeds),
+        seed: &str,
+        owner: &Pubkey,
+    ) -> AnchorResult {
+        account.check_writable()?;
+        base.check_signer()?;
+        let mut signers = SystemSigners::new();
+        base.signer_seeds(&mut signers)?;
+        let instruction = assign_with_seed_instruction(
+            account.account_info_ref(),
+            base.account_info_ref(),
+            seed,
+            owner,
+        )?;
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    /// `seed` and `owner` are the ones `from` was derived with.
+    pub fn transfer_with_seed(
+        &self,
+        from: &impl WritableAccount,
+        base: &(impl SigningAccount + SignerSeeds),
+        to: &impl WritableAccount,
+        seed: &str,
+        owner: &Pubkey,
+        lamports: u64,
+    ) -> AnchorResult {
+        from.check_writable()?;
+        base.check_signer()?;
+        to.check_writable()?;
+        let mut signers = SystemSigners::new();
+        base.signer_seeds(&mut signers)?;
+        let instruction = transfer_with_seed_instruction(
+            from.account_info_ref(),
+            base.account_info_ref(),
+            to.account_info_ref(),
+            seed,
+            owner,
+            lamports,
+        )?;
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn initialize_nonce_account(
+        &self,
+        nonce: &impl WritableAccount,
+        recent_blockhashes_sysvar: &impl SingleAccount,
+        rent_sysvar: &impl SingleAccount,
+        authority: &Pubkey,
+    ) -> AnchorResult {
+        nonce.check_writable()?;
+        initialize_nonce_account_instruction(
+            nonce.account_info_ref(),
+            recent_blockhashes_sysvar.account_info_ref(),
+            rent_sysvar.account_info_ref(),
+            authority,
+        )
+        .invoke_signed(&[])
+    }
+
+    pub fn advance_nonce_account(
+        &self,
+        nonce: &impl WritableAccount,
+        recent_blockhashes_sysvar: &impl SingleAccount,
+        authority: &(impl SigningAccount + SignerSeeds),
+    ) -> AnchorResult {
+        nonce.check_writable()?;
+        authority.check_signer()?;
+        let mut signers = SystemSigners::new();
+        authority.signer_seeds(&mut signers)?;
+        let instruction = advance_nonce_account_instruction(
+            nonce.account_info_ref(),
+            recent_blockhashes_sysvar.account_info_ref(),
+            authority.account_info_ref(),
+        );
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn withdraw_nonce_account(
+        &self,
+        nonce: &impl WritableAccount,
+        recipient: &impl WritableAccount,
+        recent_blockhashes_sysvar: &impl SingleAccount,
+        rent_sysvar: &impl SingleAccount,
+        authority: &(impl SigningAccount + SignerSeeds),
+        lamports: u64,
+    ) -> AnchorResult {
+        nonce.check_writable()?;
+        recipient.check_writable()?;
+        authority.check_signer()?;
+        let mut signers = SystemSigners::new();
+        authority.signer_seeds(&mut signers)?;
+        let instruction = withdraw_nonce_account_instruction(
+            nonce.account_info_ref(),
+            recipient.account_info_ref(),
+            recent_blockhashes_sysvar.account_info_ref(),
+            rent_sysvar.account_info_ref(),
+            authority.account_info_ref(),
+            lamports,
+        );
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn authorize_nonce_account(
+        &self,
+        nonce: &impl WritableAccount,
+        authority: &(impl SigningAccount + SignerSeeds),
+        new_authority: &Pubkey,
+    ) -> AnchorResult {
+        nonce.check_writable()?;
+        authority.check_signer()?;
+        let mut signers = SystemSigners::new();
+        authority.signer_seeds(&mut signers)?;
+        let instruction = authorize_nonce_account_instruction(
+            nonce.account_info_ref(),
+            authority.account_info_ref(),
+            new_authority,
+        );
+        signers.with_signers(|signers| instruction.invoke_signed(signers))
+    }
+
+    pub fn update_nonce_account(&self, nonce: &impl WritableAccount) -> AnchorResult {
+        nonce.check_writable()?;
+        update_nonce_account_instruction(nonce.account_info_ref()).invoke_signed(&[])
+    }
+}
+
+/// Longest instruction data, `CreateAccountWithSeed` with a [`MAX_SEED_LEN`] seed.
+const MAX_DATA_LEN: usize = 4 + 32 + 8 + MAX_SEED_LEN + 8 + 8 + 32;
+
+/// Bincode encoded `SystemInstruction`, starting with the `u32` variant index.
+struct InstructionData {
+    bytes: [u8; MAX_DATA_LEN],
+    len: usize,
+}
+impl InstructionData {
+    fn new(index: u32) -> Self {
+        Self {
+            bytes: [0; MAX_DATA_LEN],
+            len: 0,
+        }
+        .with(&index.to_le_bytes())
+    }
+
+    fn with(mut self, bytes: &[u8]) -> Self {
+        self.bytes[self.len..][..bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        self
+    }
+
+    fn with_u64(self, value: u64) -> Self {
+        self.with(&value.to_le_bytes())
+    }
+
+    /// A `String`, prefixed with its `u64` length.
+    fn with_seed(self, seed: &str) -> AnchorResult<Self> {
+        if seed.len() > MAX_SEED_LEN {
+            return Err(ProgramError::MaxSeedLengthExceeded.into());
+        }
+        Ok(self.with_u64(seed.len() as u64).with(seed.as_bytes()))
+    }
+}
+
+/// A system program instruction, built apart from invoking it.
+struct SystemInstruction<'a, const ACCOUNTS: usize> {
+    metas: [AccountMeta<'a>; ACCOUNTS],
+    accounts: [&'a AccountInfo; ACCOUNTS],
+    data: InstructionData,
+}
+impl<const ACCOUNTS: usize> SystemInstruction<'_, ACCOUNTS> {
+    fn data(&self) -> &[u8] {
+        &self.data.bytes[..self.data.len]
+    }
+
+    fn invoke_signed(&self, signers: &[Signer]) -> AnchorResult {
+        invoke_signed(
+            &Instruction {
+                program_id: &System::ID,
+                accounts: &self.metas,
+                data: self.data(),
+            },
+            &self.accounts,
+            signers,
+        )
+        .map_err(AnchorError::from)
+    }
+}
+
+fn create_account_instruction<'a>(
+    from: &'a AccountInfo,
+    to: &'a AccountInfo,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> SystemInstruction<'a, 2> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable_signer(from.key()),
+            AccountMeta::writable_signer(to.key()),
+        ],
+        accounts: [from, to],
+        data: InstructionData::new(0)
+            .with_u64(lamports)
+            .with_u64(space)
+            .with(owner),
+    }
+}
+
+fn assign_instruction<'a>(account: &'a AccountInfo, owner: &Pubkey) -> SystemInstruction<'a, 1> {
+    SystemInstruction {
+        metas: [AccountMeta::writable_signer(account.key())],
+        accounts: [account],
+        data: InstructionData::new(1).with(owner),
+    }
+}
+
+fn transfer_instruction<'a>(
+    from: &'a AccountInfo,
+    to: &'a AccountInfo,
+    lamports: u64,
+) -> SystemInstruction<'a, 2> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable_signer(from.key()),
+            AccountMeta::writable(to.key()),
+        ],
+        accounts: [from, to],
+        data: InstructionData::new(2).with_u64(lamports),
+    }
+}
+
+/// `base` is passed as the third account even if it is `from`.
+#[allow(clippy::too_many_arguments)]
+fn create_account_with_seed_instruction<'a>(
+    from: &'a AccountInfo,
+    to: &'a AccountInfo,
+    base: &'a AccountInfo,
+    seed: &str,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> AnchorResult<SystemInstruction<'a, 3>> {
+    Ok(SystemInstruction {
+        metas: [
+            AccountMeta::writable_signer(from.key()),
+            AccountMeta::writable(to.key()),
+            AccountMeta::readonly_signer(base.key()),
+        ],
+        accounts: [from, to, base],
+        data: InstructionData::new(3)
+            .with(base.key())
+            .with_seed(seed)?
+            .with_u64(lamports)
+            .with_u64(space)
+            .with(owner),
+    })
+}
+
+fn advance_nonce_account_instruction<'a>(
+    nonce: &'a AccountInfo,
+    recent_blockhashes_sysvar: &'a AccountInfo,
+    authority: &'a AccountInfo,
+) -> SystemInstruction<'a, 3> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable(nonce.key()),
+            AccountMeta::readonly(recent_blockhashes_sysvar.key()),
+            AccountMeta::readonly_signer(authority.key()),
+        ],
+        accounts: [nonce, recent_blockhashes_sysvar, authority],
+        data: InstructionData::new(4),
+    }
+}
+
+fn withdraw_nonce_account_instruction<'a>(
+    nonce: &'a AccountInfo,
+    recipient: &'a AccountInfo,
+    recent_blockhashes_sysvar: &'a AccountInfo,
+    rent_sysvar: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    lamports: u64,
+) -> SystemInstruction<'a, 5> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable(nonce.key()),
+            AccountMeta::writable(recipient.key()),
+            AccountMeta::readonly(recent_blockhashes_sysvar.key()),
+            AccountMeta::readonly(rent_sysvar.key()),
+            AccountMeta::readonly_signer(authority.key()),
+        ],
+        accounts: [
+            nonce,
+            recipient,
+            recent_blockhashes_sysvar,
+            rent_sysvar,
+            authority,
+        ],
+        data: InstructionData::new(5).with_u64(lamports),
+    }
+}
+
+fn initialize_nonce_account_instruction<'a>(
+    nonce: &'a AccountInfo,
+    recent_blockhashes_sysvar: &'a AccountInfo,
+    rent_sysvar: &'a AccountInfo,
+    authority: &Pubkey,
+) -> SystemInstruction<'a, 3> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable(nonce.key()),
+            AccountMeta::readonly(recent_blockhashes_sysvar.key()),
+            AccountMeta::readonly(rent_sysvar.key()),
+        ],
+        accounts: [nonce, recent_blockhashes_sysvar, rent_sysvar],
+        data: InstructionData::new(6).with(authority),
+    }
+}
+
+fn authorize_nonce_account_instruction<'a>(
+    nonce: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    new_authority: &Pubkey,
+) -> SystemInstruction<'a, 2> {
+    SystemInstruction {
+        metas: [
+            AccountMeta::writable(nonce.key()),
+            AccountMeta::readonly_signer(authority.key()),
+        ],
+        accounts: [nonce, authority],
+        data: InstructionData::new(7).with(new_authority),
+    }
+}
+
+fn allocate_instruction(account: &AccountInfo, space: u64) -> SystemInstruction<'_, 1> {
+    SystemInstruction {
+        metas: [AccountMeta::writable_signer(account.key())],
+        accounts: [account],
+        data: InstructionData::new(8).with_u64(space),
+    }
+}
+
+fn allocate_with_seed_instruction<'a>(
+    account: &'a AccountInfo,
+    base: &'a AccountInfo,
+    seed: &str,
+    space: u64,
+    owner: &Pubkey,
+) -> AnchorResult<SystemInstruction<'a, 2>> {
+    Ok(SystemInstruction {
+        metas: [
+            AccountMeta::writable(account.key()),
+            AccountMeta::readonly_signer(base.key()),
+        ],
+        accounts: [account, base],
+        data: InstructionData::new(9)
+            .with(base.key())
+            .with_seed(seed)?
+            .with_u64(space)
+            .with(owner),
+    })
+}
+
+fn assign_with_seed_instruction<'a>(
+    account: &'a AccountInfo,
+    base: &'a AccountInfo,
+    seed: &str,
+    owner: &Pubkey,
+) -> AnchorResult<SystemInstruction<'a, 2>> {
+    Ok(SystemInstruction {
+        metas: [
+            AccountMeta::writable(account.key()),
+            AccountMeta::readonly_signer(base.key()),
+        ],
+        accounts: [account, base],
+        data: InstructionData::new(10)
+            .with(base.key())
+            .with_seed(seed)?
+            .with(owner),
+    })
+}
+
+fn transfer_with_seed_instruction<'a>(
+    from: &'a AccountInfo,
+    base: &'a AccountInfo,
+    to: &'a AccountInfo,
+    seed: &str,
+    owner: &Pubkey,
+    lamports: u64,
+) -> AnchorResult<SystemInstruction<'a, 3>> {
+    Ok(SystemInstruction {
+        metas: [
+            AccountMeta::writable(from.key()),
+            AccountMeta::readonly_signer(base.key()),
+            AccountMeta::writable(to.key()),
+        ],
+        accounts: [from, base, to],
+        data: InstructionData::new(11)
+            .with_u64(lamports)
+            .with_seed(seed)?
+            .with(owner),
+    })
+}
+
+/// `UpgradeNonceAccount`.
+fn update_nonce_account_instruction(nonce: &AccountInfo) -> SystemInstruction<'_, 1> {
+    SystemInstruction {
+        metas: [AccountMeta::writable(nonce.key())],
+        accounts: [nonce],
+        data: InstructionData::new(12),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_util::TestAccount;
+
+    const OWNER: Pubkey = [9; 32];
+
+    /// `parts` concatenated, which must be `N` bytes.
+    fn concat<const N: usize>(parts: &[&[u8]]) -> [u8; N] {
+        let mut out = [0; N];
+        let mut len = 0;
+        for part in parts {
+            out[len..][..part.len()].copy_from_slice(part);
+            len += part.len();
+        }
+        assert_eq!(len, N);
+        out
+    }
+
+    /// The `(key, is_writable, is_signer)` of each meta, checking the infos are in the same order.
+    fn metas<const N: usize>(instruction: &SystemInstruction<N>) -> [(Pubkey, bool, bool); N] {
+        core::array::from_fn(|index| {
+            let meta = &instruction.metas[index];
+            assert_eq!(meta.pubkey, instruction.accounts[index].key());
+            (*meta.pubkey, meta.is_writable, meta.is_signer)
+        })
+    }
+
+    #[test]
+    fn create_account_layout() {
+        let [mut from, mut to] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let (from, to) = (from.info(), to.info());
+        let instruction = create_account_instruction(&from, &to, 5, 100, &OWNER);
+        assert_eq!(
+            instruction.data(),
+            concat::<52>(&[
+                &[0, 0, 0, 0],
+                &5u64.to_le_bytes(),
+                &100u64.to_le_bytes(),
+                &OWNER
+            ])
+        );
+        assert_eq!(
+            metas(&instruction),
+            [([1; 32], true, true), ([2; 32], true, true)]
+        );
+    }
+
+    #[test]
+    fn assign_and_allocate_layout() {
+        let mut account = TestAccount::new([1; 32]);
+        let account = account.info();
+
+        let instruction = assign_instruction(&account, &OWNER);
+        assert_eq!(instruction.data(), concat::<36>(&[&[1, 0, 0, 0], &OWNER]));
+        assert_eq!(metas(&instruction), [([1; 32], true, true)]);
+
+        let instruction = allocate_instruction(&account, 100);
+        assert_eq!(instruction.data(), [8, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(metas(&instruction), [([1; 32], true, true)]);
+    }
+
+    #[test]
+    fn transfer_layout() {
+        let [mut from, mut to] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let (from, to) = (from.info(), to.info());
+        let instruction = transfer_instruction(&from, &to, 0x0102);
+        assert_eq!(instruction.data(), [2, 0, 0, 0, 2, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            metas(&instruction),
+            [([1; 32], true, true), ([2; 32], true, false)]
+        );
+    }
+
+    #[test]
+    fn with_seed_layouts() {
+        let [mut from, mut to, mut base] = [1, 2, 3].map(|key| TestAccount::new([key; 32]));
+        let (from, to, base) = (from.info(), to.info(), base.info());
+        let seed_len = 4u64.to_le_bytes();
+
+        let instruction =
+            create_account_with_seed_instruction(&from, &to, &base, "seed", 5, 100, &OWNER)
+                .unwrap();
+        assert_eq!(
+            instruction.data(),
+            concat::<96>(&[
+                &[3, 0, 0, 0],
+                &[3; 32],
+                &seed_len,
+                b"seed",
+                &5u64.to_le_bytes(),
+                &100u64.to_le_bytes(),
+                &OWNER,
+            ])
+        );
+        assert_eq!(
+            metas(&instruction),
+            [
+                ([1; 32], true, true),
+                ([2; 32], true, false),
+                ([3; 32], false, true)
+            ]
+        );
+
+        let instruction = allocate_with_seed_instruction(&to, &base, "seed", 100, &OWNER).unwrap();
+        assert_eq!(
+            instruction.data(),
+            concat::<88>(&[
+                &[9, 0, 0, 0],
+                &[3; 32],
+                &seed_len,
+                b"seed",
+                &100u64.to_le_bytes(),
+                &OWNER,
+            ])
+        );
+        assert_eq!(
+            metas(&instruction),
+            [([2; 32], true, false), ([3; 32], false, true)]
+        );
+
+        let instruction = assign_with_seed_instruction(&to, &base, "seed", &OWNER).unwrap();
+        assert_eq!(
+            instruction.data(),
+            concat::<80>(&[&[10, 0, 0, 0], &[3; 32], &seed_len, b"seed", &OWNER])
+        );
+        assert_eq!(
+            metas(&instruction),
+            [([2; 32], true, false), ([3; 32], false, true)]
+        );
+
+        let instruction =
+            transfer_with_seed_instruction(&from, &base, &to, "seed", &OWNER, 5).unwrap();
+        assert_eq!(
+            instruction.data(),
+            concat::<56>(&[
+                &[11, 0, 0, 0],
+                &5u64.to_le_bytes(),
+                &seed_len,
+                b"seed",
+                &OWNER,
+            ])
+        );
+        assert_eq!(
+            metas(&instruction),
+            [
+                ([1; 32], true, false),
+                ([3; 32], false, true),
+                ([2; 32], true, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_long_seeds() {
+        let [mut account, mut base] = [1, 2].map(|key| TestAccount::new([key; 32]));
+        let (account, base) = (account.info(), base.info());
+        let seed = "0123456789abcdef0123456789abcdef";
+        let instruction =
+            create_account_with_seed_instruction(&account, &account, &base, seed, 0, 0, &OWNER)
+                .unwrap();
+        assert_eq!(instruction.data().len(), MAX_DATA_LEN);
+
+        let seed = "0123456789abcdef0123456789abcdefg";
+        assert_eq!(
+            assign_with_seed_instruction(&account, &base, seed, &OWNER)
+                .err()
+                .map(|error| error.error),
+            Some(ProgramError::MaxSeedLengthExceeded)
+        );
+    }
+
+    #[test]
+    fn nonce_layouts() {
+        let [mut nonce, mut recipient, mut blockhashes, mut rent, mut authority] =
+            [1, 2, 3, 4, 5].map(|key| TestAccount::new([key; 32]));
+        let (nonce, recipient, blockhashes, rent, authority) = (
+            nonce.info(),
+            recipient.info(),
+            blockhashes.info(),
+            rent.info(),
+            authority.info(),
+        );
+
+        let instruction = advance_nonce_account_instruction(&nonce, &blockhashes, &authority);
+        assert_eq!(instruction.data(), [4, 0, 0, 0]);
+        assert_eq!(
+            metas(&instruction),
+            [
+                ([1; 32], true, false),
+                ([3; 32], false, false),
+                ([5; 32], false, true)
+            ]
+        );
+
+        let instruction = withdraw_nonce_account_instruction(
+            &nonce,
+            &recipient,
+            &blockhashes,
+            &rent,
+            &authority,
+            5,
+        );
+        assert_eq!(instruction.data(), [5, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            metas(&instruction),
+            [
+                ([1; 32], true, false),
+                ([2; 32], true, false),
+                ([3; 32], false, false),
+                ([4; 32], false, false),
+                ([5; 32], false, true)
+            ]
+        );
+
+        let instruction =
+            initialize_nonce_account_instruction(&nonce, &blockhashes, &rent, &[5; 32]);
+        assert_eq!(instruction.data(), concat::<36>(&[&[6, 0, 0, 0], &[5; 32]]));
+        assert_eq!(
+            metas(&instruction),
+            [
+                ([1; 32], true, false),
+                ([3; 32], false, false),
+                ([4; 32], false, false)
+            ]
+        );
+
+        let instruction = authorize_nonce_account_instruction(&nonce, &authority, &[6; 32]);
+        assert_eq!(instruction.data(), concat::<36>(&[&[7, 0, 0, 0], &[6; 32]]));
+        assert_eq!(
+            metas(&instruction),
+            [([1; 32], true, false), ([5; 32], false, true)]
+        );
+
+        let instruction = update_nonce_account_instruction(&nonce);
+        assert_eq!(instruction.data(), [12, 0, 0, 0]);
+        assert_eq!(metas(&instruction), [([1; 32], true, false)]);
+    }
+
+    #[test]
+    fn checks_accounts_before_invoking() {
+        let mut program = TestAccount::new(System::ID).executable();
+        let system = Program::<System>::new_unchecked(program.info());
+        let mut from = TestAccount::new([1; 32]).writable().signer();
+        let mut to = TestAccount::new([2; 32]).writable();
+        let mut read_only = TestAccount::new([3; 32]).signer();
+
+        assert_eq!(system.transfer(&from.info(), &to.info(), 5), Ok(()));
+        assert_eq!(
+            system
+                .transfer(&from.info(), &read_only.info(), 5)
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountNotMutable.into())
+        );
+        assert_eq!(
+            system
+                .transfer(&to.info(), &from.info(), 5)
+                .map_err(|error| error.error),
+            Err(ErrorCode::AccountNotSigner.into())
+        );
+    }
+}
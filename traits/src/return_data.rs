@@ -0,0 +1,198 @@
+use crate::error::{AnchorError, AnchorResult, ErrorCode};
+use crate::traits::program::GetProgramId;
+use crate::traits::AccountsContext;
+use borsh::io::ErrorKind;
+use borsh::{BorshDeserialize, BorshSerialize};
+use pinocchio::cpi::MAX_RETURN_DATA;
+use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Error)]
+pub enum ReturnDataError {
+    #[error("no return data was set")]
+    Missing,
+    #[error("return data is larger than {MAX_RETURN_DATA} bytes")]
+    TooLarge,
+    #[error("return value could not be serialized")]
+    InvalidValue,
+    #[error("return data was set by another program")]
+    WrongProgram { expected: Pubkey, found: Pubkey },
+    #[error("return data could not be decoded")]
+    InvalidData,
+}
+impl From<ReturnDataError> for AnchorError {
+    fn from(value: ReturnDataError) -> Self {
+        match value {
+            ReturnDataError::Missing => ErrorCode::ReturnDataMissing.into(),
+            ReturnDataError::TooLarge => ErrorCode::ReturnDataTooLarge.into(),
+            ReturnDataError::InvalidValue => ErrorCode::ReturnDataDidNotSerialize.into(),
+            ReturnDataError::WrongProgram { expected, found } => {
+                AnchorError::from(ProgramError::IncorrectProgramId).with_pubkeys(found, expected)
+            }
+            ReturnDataError::InvalidData => ErrorCode::ReturnDataDidNotDeserialize.into(),
+        }
+    }
+}
+
+/// Serializes `value` as the current program's return data.
+pub fn set_return_data<T>(value: &T) -> Result<(), ReturnDataError>
+where
+    T: BorshSerialize,
+{
+    let mut buffer = [0; MAX_RETURN_DATA];
+    pinocchio::cpi::set_return_data(encode(value, &mut buffer)?);
+    Ok(())
+}
+
+/// Decodes the return data of the last CPI, which must have been set by `P`.
+pub fn get_return_data<P, T>(accounts_context: &AccountsContext) -> Result<T, ReturnDataError>
+where
+    P: GetProgramId,
+    T: BorshDeserialize,
+{
+    let return_data = pinocchio::cpi::get_return_data().ok_or(ReturnDataError::Missing)?;
+    decode(
+        P::program_id(accounts_context),
+        return_data.program_id(),
+        return_data.as_slice(),
+    )
+}
+
+/// Runs an instruction handler and sets its output as the return data.
+pub fn with_return_data<T>(handler: impl FnOnce() -> AnchorResult<T>) -> AnchorResult
+where
+    T: BorshSerialize,
+{
+    set_return_data(&handler()?)?;
+    Ok(())
+}
+
+/// Serializes `value` into `buffer`, returning the written part.
+fn encode<'a, T>(
+    value: &T,
+    buffer: &'a mut [u8; MAX_RETURN_DATA],
+) -> Result<&'a [u8], ReturnDataError>
+where
+    T: BorshSerialize,
+{
+    let mut remaining = buffer.as_mut_slice();
+    borsh::to_writer(&mut remaining, value).map_err(|error| match error.kind() {
+        // Writing to a slice fails with `WriteZero` once it is full.
+        ErrorKind::WriteZero => ReturnDataError::TooLarge,
+        _ => ReturnDataError::InvalidValue,
+    })?;
+    let len = MAX_RETURN_DATA - remaining.len();
+    Ok(&buffer[..len])
+}
+
+fn decode<T>(expected: &Pubkey, found: &Pubkey, data: &[u8]) -> Result<T, ReturnDataError>
+where
+    T: BorshDeserialize,
+{
+    if found != expected {
+        return Err(ReturnDataError::WrongProgram {
+            expected: *expected,
+            found: *found,
+        });
+    }
+    T::try_from_slice(data).map_err(|_| ReturnDataError::InvalidData)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::program::CurrentProgram;
+    use borsh::io::{Error, Write};
+
+    /// Fails to serialize for a reason other than its size.
+    struct Unserializable;
+    impl BorshSerialize for Unserializable {
+        fn serialize<W: Write>(&self, _writer: &mut W) -> borsh::io::Result<()> {
+            Err(Error::new(ErrorKind::InvalidData, "unserializable"))
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut buffer = [0; MAX_RETURN_DATA];
+        let data = encode(&(7u64, [1u8; 3]), &mut buffer).unwrap();
+        assert_eq!(data, [7, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1]);
+        assert_eq!(
+            decode::<(u64, [u8; 3])>(&[1; 32], &[1; 32], data),
+            Ok((7, [1; 3]))
+        );
+
+        let data = encode(&[2u8; MAX_RETURN_DATA], &mut buffer).unwrap();
+        assert_eq!(data.len(), MAX_RETURN_DATA);
+    }
+
+    #[test]
+    fn encode_errors() {
+        let mut buffer = [0; MAX_RETURN_DATA];
+        assert_eq!(
+            encode(&[0u8; MAX_RETURN_DATA + 1], &mut buffer),
+            Err(ReturnDataError::TooLarge)
+        );
+        assert_eq!(
+            encode(&Unserializable, &mut buffer),
+            Err(ReturnDataError::InvalidValue)
+        );
+        assert_eq!(
+            set_return_data(&[0u8; MAX_RETURN_DATA + 1]),
+            Err(ReturnDataError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            decode::<u64>(&[1; 32], &[2; 32], &[0; 8]),
+            Err(ReturnDataError::WrongProgram {
+                expected: [1; 32],
+                found: [2; 32]
+            })
+        );
+        assert_eq!(
+            decode::<u64>(&[1; 32], &[1; 32], &[0; 4]),
+            Err(ReturnDataError::InvalidData)
+        );
+        // Trailing bytes are rejected.
+        assert_eq!(
+            decode::<u64>(&[1; 32], &[1; 32], &[0; 9]),
+            Err(ReturnDataError::InvalidData)
+        );
+        // No CPI has returned data on the host.
+        assert_eq!(
+            get_return_data::<CurrentProgram, u64>(&AccountsContext::new(&[1; 32])),
+            Err(ReturnDataError::Missing)
+        );
+    }
+
+    #[test]
+    fn errors_have_distinct_codes() {
+        let codes = [
+            ReturnDataError::Missing,
+            ReturnDataError::TooLarge,
+            ReturnDataError::InvalidValue,
+            ReturnDataError::InvalidData,
+        ]
+        .map(|error| AnchorError::from(error).error_code());
+        assert_eq!(
+            codes,
+            [
+                Some(ErrorCode::ReturnDataMissing),
+                Some(ErrorCode::ReturnDataTooLarge),
+                Some(ErrorCode::ReturnDataDidNotSerialize),
+                Some(ErrorCode::ReturnDataDidNotDeserialize),
+            ]
+        );
+        assert_eq!(
+            AnchorError::from(ReturnDataError::WrongProgram {
+                expected: [1; 32],
+                found: [2; 32]
+            }),
+            AnchorError::from(ProgramError::IncorrectProgramId).with_pubkeys([2; 32], [1; 32])
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Account buffers laid out like the runtime's input, for unit tests.
+
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::account::{Accounts, DecodeAccounts, ValidateAccounts};
+use crate::traits::constraint::AccountInfoConstraint;
+use crate::traits::AccountsContext;
+use core::iter::empty;
+use core::mem::transmute;
+use pinocchio::account_info::AccountInfo;
+use pinocchio::instruction::AccountMeta;
+use pinocchio::pubkey::Pubkey;
+
+const HEADER_LEN: usize = 88;
+const DATA_CAPACITY: usize = 512;
+
+/// A serialized account, `info` must not be used after `self` moves.
+#[repr(C, align(8))]
+pub struct TestAccount {
+    bytes: [u8; HEADER_LEN + DATA_CAPACITY],
+}
+impl TestAccount {
+    pub fn new(key: Pubkey) -> Self {
+        let mut account = Self {
+            bytes: [0; HEADER_LEN + DATA_CAPACITY],
+        };
+        // Not borrowed.
+        account.bytes[0] = u8::MAX;
+        account.bytes[8..40].copy_from_slice(&key);
+        account
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.bytes[1] = 1;
+        self
+    }
+
+    pub fn writable(mut self) -> Self {
+        self.bytes[2] = 1;
+        self
+    }
+
+    /// Marks the data as mutably borrowed, without an actual borrow.
+    #[cfg(feature = "borsh")]
+    pub fn data_borrowed_mut(mut self) -> Self {
+        self.bytes[0] &= 0b_1111_0111;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.bytes[3] = 1;
+        self
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.bytes[40..72].copy_from_slice(&owner);
+        self
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.bytes[72..80].copy_from_slice(&lamports.to_le_bytes());
+        self
+    }
+
+    /// Panics if `data` is longer than the buffer.
+    pub fn data(mut self, data: &[u8]) -> Self {
+        self.bytes[80..88].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        self.bytes[HEADER_LEN..][..data.len()].copy_from_slice(data);
+        self
+    }
+
+    pub fn info(&mut self) -> AccountInfo {
+        // Safety: `AccountInfo` is a pointer to the header, which `bytes` matches.
+        unsafe { transmute::<*mut u8, AccountInfo>(self.bytes.as_mut_ptr()) }
+    }
+}
+
+/// Fails only its early validation, with [`ErrorCode::ConstraintRaw`].
+pub struct FailsEarly;
+impl AccountInfoConstraint for FailsEarly {
+    fn early_validation(
+        &mut self,
+        _account: &AccountInfo,
+        _context: &mut AccountsContext,
+    ) -> AnchorResult {
+        Err(ErrorCode::ConstraintRaw.into())
+    }
+}
+
+/// Decodes from no accounts, but hints it may take up to `HINT`.
+#[derive(Debug)]
+pub struct NoAccounts<const HINT: usize = 0>;
+impl<const HINT: usize> Accounts for NoAccounts<HINT> {
+    fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+        empty()
+    }
+
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+        empty()
+    }
+}
+impl<const HINT: usize> DecodeAccounts<()> for NoAccounts<HINT> {
+    fn try_accounts(
+        _accounts_context: &mut AccountsContext,
+        _accounts: &mut impl Iterator<Item = AccountInfo>,
+        _arg: (),
+    ) -> AnchorResult<Self> {
+        Ok(Self)
+    }
+
+    fn size_hint() -> (usize, Option<usize>) {
+        (0, Some(HINT))
+    }
+}
+impl<const HINT: usize> ValidateAccounts<()> for NoAccounts<HINT> {
+    fn validate(&mut self, _accounts_context: &mut AccountsContext, _arg: ()) -> AnchorResult {
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use crate::error::AnchorResult;
-use crate::traits::maybe_bool::MaybeBool;
+use crate::error::{AnchorResult, ErrorCode};
+use crate::traits::maybe_bool::{MaybeBool, MaybeTrue};
 use crate::traits::AccountsContext;
 use pinocchio::account_info::AccountInfo;
 use pinocchio::instruction::AccountMeta;
@@ -35,6 +35,107 @@ pub unsafe trait SingleAccount: Accounts {
     }
 }
 
+/// An account that may be written to, [`Unknown`](crate::traits::maybe_bool::Unknown)
+/// mutability is checked at runtime.
+///
+/// ```
+/// use pinocchio::account_info::AccountInfo;
+/// use traits::accounts::mutable::Mut;
+/// use traits::traits::account::WritableAccount;
+///
+/// fn destination(_: &impl WritableAccount) {}
+///
+/// fn accepted(writable: &Mut, unknown: &AccountInfo) {
+///     destination(writable);
+///     destination(unknown);
+/// }
+/// ```
+///
+/// A [`ReadOnly`](crate::accounts::mutable::ReadOnly) account is rejected at compile time:
+///
+/// ```compile_fail,E0271
+/// use traits::accounts::mutable::ReadOnly;
+/// use traits::traits::account::WritableAccount;
+///
+/// fn destination(_: &impl WritableAccount) {}
+///
+/// fn rejected(read_only: &ReadOnly) {
+///     destination(read_only);
+/// }
+/// ```
+pub trait WritableAccount: SingleAccount<Mutable: MaybeTrue> {
+    #[inline]
+    fn check_writable(&self) -> AnchorResult {
+        if Self::Mutable::IS_TRUE || self.account_info_ref().is_writable() {
+            Ok(())
+        } else {
+            Err(ErrorCode::AccountNotMutable.into())
+        }
+    }
+}
+impl<T> WritableAccount for T where T: SingleAccount<Mutable: MaybeTrue> {}
+
+/// An account that may sign, [`Unknown`](crate::traits::maybe_bool::Unknown) signers are checked
+/// at runtime.
+///
+/// ```
+/// use pinocchio::account_info::AccountInfo;
+/// use traits::accounts::signer::Signer;
+/// use traits::traits::account::SigningAccount;
+///
+/// fn authority(_: &impl SigningAccount) {}
+///
+/// fn accepted(signer: &Signer, unknown: &AccountInfo) {
+///     authority(signer);
+///     authority(unknown);
+/// }
+/// ```
+///
+/// An account with `CanSign = False` is rejected at compile time:
+///
+/// ```compile_fail,E0271
+/// # use pinocchio::account_info::AccountInfo;
+/// # use pinocchio::instruction::AccountMeta;
+/// # use traits::traits::account::{Accounts, SingleAccount};
+/// # use traits::traits::maybe_bool::{False, Unknown};
+/// use traits::traits::account::SigningAccount;
+///
+/// struct NotSigner(AccountInfo);
+/// # impl Accounts for NotSigner {
+/// #     fn to_account_metas(&self, is_signer: Option<bool>) -> impl Iterator<Item = AccountMeta<'_>> {
+/// #         self.0.to_account_metas(is_signer)
+/// #     }
+/// #     fn to_account_infos(&self) -> impl Iterator<Item = AccountInfo> {
+/// #         self.0.to_account_infos()
+/// #     }
+/// # }
+/// unsafe impl SingleAccount for NotSigner {
+///     type Mutable = Unknown;
+///     type CanSign = False;
+///
+///     fn account_info_ref(&self) -> &AccountInfo {
+///         &self.0
+///     }
+/// }
+///
+/// fn authority(_: &impl SigningAccount) {}
+///
+/// fn rejected(not_signer: &NotSigner) {
+///     authority(not_signer);
+/// }
+/// ```
+pub trait SigningAccount: SingleAccount<CanSign: MaybeTrue> {
+    #[inline]
+    fn check_signer(&self) -> AnchorResult {
+        if Self::CanSign::IS_TRUE || self.account_info_ref().is_signer() {
+            Ok(())
+        } else {
+            Err(ErrorCode::AccountNotSigner.into())
+        }
+    }
+}
+impl<T> SigningAccount for T where T: SingleAccount<CanSign: MaybeTrue> {}
+
 pub trait DecodeAccounts<A>: Sized + Accounts {
     #[track_caller]
     fn try_accounts(
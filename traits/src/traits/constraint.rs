@@ -1,6 +1,9 @@
-use crate::error::AnchorResult;
+use crate::constraints::all::impl_supports_constraint_tuples;
+use crate::constraints::with_error::WithError;
+use crate::error::{AnchorResult, CustomErrorGenerator};
 use crate::traits::account::Accounts;
 use crate::traits::AccountsContext;
+use pinocchio::account_info::AccountInfo;
 
 #[allow(unused_variables)]
 pub trait SupportsConstraint<C>: Accounts {
@@ -24,3 +27,74 @@ pub trait SupportsConstraint<C>: Accounts {
         Ok(())
     }
 }
+
+/// A constraint that only needs the [`AccountInfo`] of an account. Supported by [`AccountInfo`]
+/// and so by every wrapper and data account that forwards constraints to its inner account.
+#[allow(unused_variables)]
+pub trait AccountInfoConstraint {
+    fn early_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        Ok(())
+    }
+
+    fn late_validation(
+        &mut self,
+        account: &AccountInfo,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        Ok(())
+    }
+
+    fn cleanup(&mut self, account: &AccountInfo, context: &mut AccountsContext) -> AnchorResult {
+        Ok(())
+    }
+
+    /// Replaces the errors of this constraint using `error`.
+    #[inline]
+    fn with_error<E>(self, error: E) -> WithError<Self, E>
+    where
+        Self: Sized,
+        E: CustomErrorGenerator + Clone,
+    {
+        WithError {
+            constraint: self,
+            error,
+        }
+    }
+}
+impl<C> SupportsConstraint<C> for AccountInfo
+where
+    C: AccountInfoConstraint,
+{
+    #[inline]
+    fn early_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        constraint.early_validation(self, context)
+    }
+
+    #[inline]
+    fn late_validation(
+        &mut self,
+        constraint: &mut C,
+        context: &mut AccountsContext,
+    ) -> AnchorResult {
+        constraint.late_validation(self, context)
+    }
+
+    #[inline]
+    fn cleanup(&mut self, constraint: &mut C, context: &mut AccountsContext) -> AnchorResult {
+        constraint.cleanup(self, context)
+    }
+}
+impl_supports_constraint_tuples!(impl[] AccountInfo where []);
+
+/// A constraint checked against the decoded data `T` of a data account.
+pub trait DataConstraint<T> {
+    fn validate_data(&mut self, data: &T, context: &mut AccountsContext) -> AnchorResult;
+}
@@ -12,6 +12,8 @@ pub trait MaybeBool: Sealed + Sized {
 pub trait Bool: MaybeBool {
     const VALUE: bool = Self::MAYBE_VALUE.unwrap();
 }
+/// [`True`] or [`Unknown`], for requirements that are checked at runtime when not known.
+pub trait MaybeTrue: MaybeBool {}
 
 mod sealed {
     pub trait Sealed {}
@@ -35,6 +37,7 @@ impl MaybeBool for True {
 impl Bool for True {
     const VALUE: bool = true;
 }
+impl MaybeTrue for True {}
 impl<T: MaybeBool> BitOr<T> for True {
     type Output = True;
 
@@ -105,6 +108,7 @@ impl sealed::Sealed for Unknown {}
 impl MaybeBool for Unknown {
     const MAYBE_VALUE: Option<bool> = None;
 }
+impl MaybeTrue for Unknown {}
 impl<T> BitOr<T> for Unknown
 where
     T: Bool,
@@ -1,3 +1,9 @@
+#[cfg(feature = "alloc")]
+use crate::diagnostics::{Diagnostic, Diagnostics};
+use crate::error::{AnchorError, AnchorResult};
+#[cfg(feature = "alloc")]
+use core::any::type_name;
+use core::panic::Location;
 use pinocchio::pubkey::Pubkey;
 
 pub mod account;
@@ -5,9 +11,71 @@ pub mod account_data;
 pub mod constraint;
 pub mod maybe_bool;
 pub mod program;
+pub mod seed_schema;
 pub mod seeds;
+pub mod signer_seeds;
 
 #[non_exhaustive]
 pub struct AccountsContext<'a> {
     pub current_program_id: &'a Pubkey,
+    /// Collect-all mode, validation failures are recorded here instead of returned. Only set
+    /// within [`AccountsContext::collect_all`].
+    #[cfg(feature = "alloc")]
+    pub(crate) diagnostics: Option<Diagnostics>,
+}
+impl<'a> AccountsContext<'a> {
+    #[inline]
+    pub const fn new(current_program_id: &'a Pubkey) -> Self {
+        Self {
+            current_program_id,
+            #[cfg(feature = "alloc")]
+            diagnostics: None,
+        }
+    }
+
+    /// Runs `f` in collect-all mode, see [`Diagnostics`]. Returns the first recorded failure, or
+    /// the result of `f` if none were recorded, alongside every recorded failure.
+    #[cfg(feature = "alloc")]
+    pub fn collect_all<O>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> AnchorResult<O>,
+    ) -> (AnchorResult<O>, Diagnostics) {
+        let outer = self.diagnostics.replace(Diagnostics::default());
+        let result = f(self);
+        let diagnostics = core::mem::replace(&mut self.diagnostics, outer).unwrap_or_default();
+        (diagnostics.result().and(result), diagnostics)
+    }
+
+    /// Whether failures are being collected, see [`AccountsContext::collect_all`].
+    #[inline]
+    pub fn is_collecting_all(&self) -> bool {
+        #[cfg(feature = "alloc")]
+        return self.diagnostics.is_some();
+        #[cfg(not(feature = "alloc"))]
+        false
+    }
+
+    /// Reports a validation failure of `T` on `key`. Returns the error unless in collect-all
+    /// mode, where it is recorded and validation continues. The failure is then returned by
+    /// [`AccountsContext::collect_all`].
+    pub fn report<T: ?Sized>(
+        &mut self,
+        error: AnchorError,
+        location: &'static Location<'static>,
+        key: Option<&Pubkey>,
+    ) -> AnchorResult {
+        #[cfg(feature = "alloc")]
+        if let Some(diagnostics) = &mut self.diagnostics {
+            diagnostics.failures.push(Diagnostic {
+                account_index: key
+                    .and_then(|key| diagnostics.decoded_keys.iter().position(|k| k == key)),
+                key: key.copied(),
+                wrapper: type_name::<T>(),
+                error,
+                location,
+            });
+            return Ok(());
+        }
+        Err(error.logged(location, key))
+    }
 }
@@ -0,0 +1,245 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use pinocchio::pubkey::MAX_SEED_LEN;
+
+/// The shape of one seed of a [`Seeds`](crate::traits::seeds::Seeds) type.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SeedSchema {
+    /// A constant seed.
+    Literal(&'static [u8]),
+    /// Any bytes of a fixed length, e.g. `32` for a pubkey or `8` for a `u64`.
+    Fixed(usize),
+    /// Any bytes up to [`MAX_SEED_LEN`](pinocchio::pubkey::MAX_SEED_LEN).
+    Variable,
+}
+
+/// One byte of an address' preimage.
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone)]
+struct SchemaByte {
+    /// `None` if any byte.
+    value: Option<u8>,
+    /// Can be left out, used for the bytes of [`SeedSchema::Variable`].
+    optional: bool,
+}
+
+#[cfg(feature = "alloc")]
+fn schema_bytes(schema: &[SeedSchema]) -> Vec<SchemaByte> {
+    schema
+        .iter()
+        .flat_map(|seed| -> Vec<SchemaByte> {
+            match *seed {
+                SeedSchema::Literal(literal) => literal
+                    .iter()
+                    .map(|&value| SchemaByte {
+                        value: Some(value),
+                        optional: false,
+                    })
+                    .collect(),
+                SeedSchema::Fixed(len) => vec![
+                    SchemaByte {
+                        value: None,
+                        optional: false,
+                    };
+                    len
+                ],
+                SeedSchema::Variable => vec![
+                    SchemaByte {
+                        value: None,
+                        optional: true,
+                    };
+                    MAX_SEED_LEN
+                ],
+            }
+        })
+        .collect()
+}
+
+/// Whether seeds of schema `a` and `b` can derive the same address.
+///
+/// Seeds are concatenated before hashing, so this is whether some byte string matches both
+/// schemas, e.g. `[b"ab", u64]` and `[b"a", pubkey]` can't collide but `[b"ab", u64]` and
+/// `[b"a", Variable]` can. The bump is left out of the schemas, as one more byte at the end of
+/// both it can't make or break a collision.
+#[cfg(feature = "alloc")]
+pub fn seeds_may_collide(a: &[SeedSchema], b: &[SeedSchema]) -> bool {
+    let a = schema_bytes(a);
+    let b = schema_bytes(b);
+    let width = b.len() + 1;
+    let mut visited = vec![false; (a.len() + 1) * width];
+    let mut stack = vec![(0, 0)];
+    while let Some((i, j)) = stack.pop() {
+        if core::mem::replace(&mut visited[i * width + j], true) {
+            continue;
+        }
+        if i == a.len() && j == b.len() {
+            return true;
+        }
+        if let Some(a) = a.get(i) {
+            if a.optional {
+                stack.push((i + 1, j));
+            }
+            if let Some(b) = b.get(j) {
+                let compatible = match (a.value, b.value) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => true,
+                };
+                if compatible {
+                    stack.push((i + 1, j + 1));
+                }
+            }
+        }
+        if b.get(j).is_some_and(|b| b.optional) {
+            stack.push((i, j + 1));
+        }
+    }
+    false
+}
+
+/// The first pair of `schemas` whose seeds may collide, see [`seeds_may_collide`]. `None`
+/// schemas are skipped.
+#[cfg(feature = "alloc")]
+pub fn find_seed_collision<'a, T>(
+    schemas: &'a [(T, Option<&[SeedSchema]>)],
+) -> Option<(&'a T, &'a T)> {
+    schemas
+        .iter()
+        .enumerate()
+        .find_map(|(index, (a, a_schema))| {
+            let a_schema = (*a_schema)?;
+            schemas[index + 1..]
+                .iter()
+                .filter_map(|(b, b_schema)| Some((b, (*b_schema)?)))
+                .find(|(_, b_schema)| seeds_may_collide(a_schema, b_schema))
+                .map(|(b, _)| (a, b))
+        })
+}
+
+/// Panics if the seeds of any two of the [`SeededAccount`](crate::traits::seeds::SeededAccount)
+/// types may collide. Types whose seeds have no [`Seeds::SCHEMA`](crate::traits::seeds::Seeds::SCHEMA)
+/// are skipped.
+///
+/// ```ignore
+/// #[test]
+/// fn seeds_do_not_collide() {
+///     assert_no_seed_collisions!(Position, Market, Vault);
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! assert_no_seed_collisions {
+    ($($account:ty),+ $(,)?) => {{
+        let schemas = [$((
+            ::core::any::type_name::<$account>(),
+            <<$account as $crate::traits::seeds::SeededAccount>::Seeds as $crate::traits::seeds::Seeds>::SCHEMA,
+        )),+];
+        if let Some((a, b)) = $crate::traits::seed_schema::find_seed_collision(&schemas) {
+            panic!("seeds of `{}` and `{}` may collide", a, b);
+        }
+    }};
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use SeedSchema::*;
+
+    #[test]
+    fn distinct_literals_do_not_collide() {
+        assert!(!seeds_may_collide(
+            &[Literal(b"vault"), Fixed(32)],
+            &[Literal(b"market"), Fixed(32)]
+        ));
+        assert!(seeds_may_collide(
+            &[Literal(b"vault"), Fixed(32)],
+            &[Literal(b"vault"), Fixed(32)]
+        ));
+    }
+
+    #[test]
+    fn literal_can_be_split_across_seeds() {
+        assert!(seeds_may_collide(
+            &[Literal(b"ab"), Fixed(8)],
+            &[Literal(b"a"), Literal(b"b"), Fixed(8)]
+        ));
+        assert!(!seeds_may_collide(
+            &[Literal(b"ab"), Fixed(8)],
+            &[Literal(b"a"), Fixed(32)]
+        ));
+    }
+
+    #[test]
+    fn fixed_lengths_must_add_up() {
+        assert!(seeds_may_collide(
+            &[Literal(b"a"), Fixed(32)],
+            &[Literal(b"a"), Fixed(24), Fixed(8)]
+        ));
+        assert!(!seeds_may_collide(
+            &[Literal(b"a"), Fixed(32)],
+            &[Literal(b"a"), Fixed(8)]
+        ));
+    }
+
+    #[test]
+    fn variable_seeds_shift_boundaries() {
+        assert!(seeds_may_collide(
+            &[Literal(b"a"), Fixed(8)],
+            &[Literal(b"a"), Variable]
+        ));
+        // The variable seed can take the literal's bytes.
+        assert!(seeds_may_collide(
+            &[Literal(b"ab"), Fixed(8)],
+            &[Literal(b"a"), Variable]
+        ));
+        assert!(seeds_may_collide(
+            &[Variable, Literal(b"x")],
+            &[Fixed(4), Literal(b"x")]
+        ));
+        assert!(!seeds_may_collide(
+            &[Variable, Literal(b"x")],
+            &[Fixed(4), Literal(b"y")]
+        ));
+        // A variable seed is at most `MAX_SEED_LEN` bytes.
+        assert!(!seeds_may_collide(&[Variable], &[Fixed(MAX_SEED_LEN + 1)]));
+        assert!(seeds_may_collide(&[Variable], &[]));
+    }
+
+    #[test]
+    fn bump_does_not_change_collisions() {
+        let cases: [(&[SeedSchema], &[SeedSchema]); 4] = [
+            (&[Literal(b"vault")], &[Literal(b"vault"), Fixed(1)]),
+            (&[Literal(b"ab"), Fixed(8)], &[Literal(b"a"), Variable]),
+            (&[Literal(b"a"), Fixed(32)], &[Literal(b"a"), Fixed(8)]),
+            (&[Variable], &[Fixed(MAX_SEED_LEN + 1)]),
+        ];
+        for (a, b) in cases {
+            let with_bump = |schema: &[SeedSchema]| -> Vec<SeedSchema> {
+                schema.iter().copied().chain([Fixed(1)]).collect()
+            };
+            assert_eq!(
+                seeds_may_collide(a, b),
+                seeds_may_collide(&with_bump(a), &with_bump(b))
+            );
+        }
+        // A `u8` seed is not mistaken for the other account's bump.
+        assert!(!seeds_may_collide(
+            &[Literal(b"vault"), Fixed(1), Fixed(1)],
+            &[Literal(b"vault"), Fixed(1)]
+        ));
+    }
+
+    #[test]
+    fn finds_first_collision() {
+        let schemas: [(&str, Option<&[SeedSchema]>); 4] = [
+            ("vault", Some(&[Literal(b"vault"), Fixed(32)])),
+            ("unknown", None),
+            ("market", Some(&[Literal(b"market"), Fixed(32)])),
+            ("any", Some(&[Literal(b"vault"), Variable])),
+        ];
+        assert_eq!(find_seed_collision(&schemas), Some((&"vault", &"any")));
+        assert_eq!(find_seed_collision(&schemas[..3]), None);
+    }
+}
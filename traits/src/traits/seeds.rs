@@ -1,14 +1,298 @@
-use alloc::vec::Vec;
-use pinocchio::pubkey::{find_program_address, Pubkey};
+use crate::error::AnchorResult;
+use crate::traits::account::SingleAccount;
+use crate::traits::seed_schema::SeedSchema;
+use crate::traits::AccountsContext;
+use crate::util::find_program_address;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::{ptr, slice};
+use derive_where::derive_where;
+use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
 
+/// Seeds of a program derived address, implemented for tuples of up to 15 [`AsSeed`]s:
+///
+/// ```
+/// use traits::traits::seeds::Seeds;
+///
+/// let seeds = (*b"vault", 7u64);
+/// seeds.with_seeds(|seeds| assert_eq!(seeds, [&b"vault"[..], &7u64.to_le_bytes()]));
+/// ```
+///
+/// A seed longer than [`MAX_SEED_LEN`] fails to compile:
+///
+/// ```compile_fail,E0080
+/// use traits::traits::seeds::Seeds;
+///
+/// (*b"vault", [0u8; 33]).with_seeds(|_| ());
+/// ```
 pub trait Seeds {
+    /// The shape of the seeds, used to check seeds of different accounts can't collide. `None`
+    /// if unknown.
+    const SCHEMA: Option<&'static [SeedSchema]> = None;
+
     fn with_seeds<O>(&self, f: impl FnOnce(&[&[u8]]) -> O) -> O;
     fn with_seeds_and_bump<O>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> O) -> O;
-    fn seeds(&self) -> Vec<&[u8]>;
+    fn seeds(&self) -> impl Iterator<Item = &[u8]>;
     fn find_program_address(&self, program_id: &Pubkey) -> (Pubkey, u8) {
         self.with_seeds(|seeds| find_program_address(seeds, program_id))
     }
+    /// Like [`find_program_address`](Self::find_program_address), but fails if there is no room
+    /// for the bump or a seed is longer than [`MAX_SEED_LEN`].
+    fn try_find_program_address(&self, program_id: &Pubkey) -> AnchorResult<(Pubkey, u8)> {
+        self.with_seeds(|seeds| {
+            if seeds.len() >= MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+                return Err(ProgramError::MaxSeedLengthExceeded.into());
+            }
+            Ok(find_program_address(seeds, program_id))
+        })
+    }
+}
+/// An account whose data records the bump of its address, so its seeds can be verified with
+/// [`create_program_address`](pinocchio::pubkey::create_program_address).
+pub trait StoredBump {
+    fn stored_bump(&self) -> AnchorResult<u8>;
+}
+/// Account data its seeds are computed from, see
+/// [`SeedsFromData`](crate::accounts::seeded::SeedsFromData).
+pub trait DataSeeds {
+    type Seeds: Seeds;
+
+    fn data_seeds(&self, context: &AccountsContext) -> AnchorResult<Self::Seeds>;
 }
 pub trait SeededAccount {
     type Seeds: Seeds;
 }
+/// A value usable as one seed of a tuple of seeds, e.g.
+/// `Seeded<T, (Const<Vault>, Pubkey, u64)>`.
+pub trait AsSeed {
+    const SCHEMA: SeedSchema;
+
+    fn as_seed(&self) -> &[u8];
+}
+/// A constant seed for [`Const`].
+pub trait ConstSeed {
+    const SEED: &'static [u8];
+}
+/// The seed of `S`, see [`ConstSeed`].
+#[derive_where(Copy, Clone, Debug, Default)]
+pub struct Const<S>(PhantomData<fn() -> S>);
+impl<S> AsSeed for Const<S>
+where
+    S: ConstSeed,
+{
+    const SCHEMA: SeedSchema = SeedSchema::Literal(S::SEED);
+
+    #[inline]
+    fn as_seed(&self) -> &[u8] {
+        S::SEED
+    }
+}
+/// Also covers [`Pubkey`].
+impl<const N: usize> AsSeed for [u8; N] {
+    const SCHEMA: SeedSchema = SeedSchema::Fixed(N);
+
+    #[inline]
+    fn as_seed(&self) -> &[u8] {
+        self
+    }
+}
+impl AsSeed for &[u8] {
+    const SCHEMA: SeedSchema = SeedSchema::Variable;
+
+    #[inline]
+    fn as_seed(&self) -> &[u8] {
+        self
+    }
+}
+impl AsSeed for &str {
+    const SCHEMA: SeedSchema = SeedSchema::Variable;
+
+    #[inline]
+    fn as_seed(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+/// Uses the account's key.
+impl<T> AsSeed for T
+where
+    T: SingleAccount,
+{
+    const SCHEMA: SeedSchema = SeedSchema::Fixed(size_of::<Pubkey>());
+
+    #[inline]
+    fn as_seed(&self) -> &[u8] {
+        self.key()
+    }
+}
+
+macro_rules! impl_as_seed_int {
+    ($($int:ty),+) => {
+        $(
+            /// Little endian, like `to_le_bytes`.
+            #[cfg(target_endian = "little")]
+            impl AsSeed for $int {
+                const SCHEMA: SeedSchema = SeedSchema::Fixed(size_of::<$int>());
+
+                #[inline]
+                fn as_seed(&self) -> &[u8] {
+                    // Safety: Integers have no padding and are stored little endian.
+                    unsafe { slice::from_raw_parts(ptr::from_ref(self).cast(), size_of::<$int>()) }
+                }
+            }
+        )+
+    };
+}
+impl_as_seed_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Fails to compile if a seed of `S` is longer than [`MAX_SEED_LEN`].
+const fn assert_seed_lens<S: Seeds>() {
+    if let Some(schema) = S::SCHEMA {
+        let mut index = 0;
+        while index < schema.len() {
+            let len = match schema[index] {
+                SeedSchema::Literal(literal) => literal.len(),
+                SeedSchema::Fixed(len) => len,
+                SeedSchema::Variable => 0,
+            };
+            assert!(len <= MAX_SEED_LEN, "seed is longer than `MAX_SEED_LEN`");
+            index += 1;
+        }
+    }
+}
+
+/// Implemented for up to 15 seeds, leaving room for the bump.
+macro_rules! impl_seeds_tuple {
+    ($($seed:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($seed),+> Seeds for ($($seed,)+)
+        where
+            $($seed: AsSeed,)+
+        {
+            const SCHEMA: Option<&'static [SeedSchema]> = Some(&[$($seed::SCHEMA),+]);
+
+            #[inline]
+            fn with_seeds<O>(&self, f: impl FnOnce(&[&[u8]]) -> O) -> O {
+                const { assert_seed_lens::<Self>() };
+                let ($($seed,)+) = self;
+                f(&[$($seed.as_seed()),+])
+            }
+
+            #[inline]
+            fn with_seeds_and_bump<O>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> O) -> O {
+                const { assert_seed_lens::<Self>() };
+                let ($($seed,)+) = self;
+                f(&[$($seed.as_seed(),)+ slice::from_ref(&bump)])
+            }
+
+            fn seeds(&self) -> impl Iterator<Item = &[u8]> {
+                const { assert_seed_lens::<Self>() };
+                let ($($seed,)+) = self;
+                [$($seed.as_seed()),+].into_iter()
+            }
+        }
+    };
+}
+impl_seeds_tuple!(S1);
+impl_seeds_tuple!(S1, S2);
+impl_seeds_tuple!(S1, S2, S3);
+impl_seeds_tuple!(S1, S2, S3, S4);
+impl_seeds_tuple!(S1, S2, S3, S4, S5);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12, S13);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12, S13, S14);
+impl_seeds_tuple!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12, S13, S14, S15);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestAccount;
+    use pinocchio::account_info::AccountInfo;
+
+    struct VaultSeed;
+    impl ConstSeed for VaultSeed {
+        const SEED: &'static [u8] = b"vault";
+    }
+
+    #[test]
+    fn as_seed_impls() {
+        assert_eq!(Const::<VaultSeed>::default().as_seed(), b"vault");
+        assert_eq!(Const::<VaultSeed>::SCHEMA, SeedSchema::Literal(b"vault"));
+        assert_eq!([1, 2, 3].as_seed(), [1, 2, 3]);
+        assert_eq!(<[u8; 3]>::SCHEMA, SeedSchema::Fixed(3));
+        assert_eq!((&[1, 2][..]).as_seed(), [1, 2]);
+        assert_eq!(<&[u8]>::SCHEMA, SeedSchema::Variable);
+        assert_eq!("seed".as_seed(), b"seed");
+        assert_eq!(<&str>::SCHEMA, SeedSchema::Variable);
+
+        let mut account = TestAccount::new([1; 32]);
+        let info = account.info();
+        assert_eq!(info.as_seed(), [1; 32]);
+        assert_eq!(AccountInfo::SCHEMA, SeedSchema::Fixed(32));
+    }
+
+    #[test]
+    fn int_seeds_are_little_endian() {
+        assert_eq!(7u8.as_seed(), [7]);
+        assert_eq!(0x0102u16.as_seed(), [2, 1]);
+        assert_eq!(0x0102_0304u32.as_seed(), 0x0102_0304u32.to_le_bytes());
+        assert_eq!(u64::MAX.as_seed(), [u8::MAX; 8]);
+        assert_eq!(1u128.as_seed(), 1u128.to_le_bytes());
+        assert_eq!((-2i8).as_seed(), [0xFE]);
+        assert_eq!((-2i16).as_seed(), (-2i16).to_le_bytes());
+        assert_eq!((-2i32).as_seed(), (-2i32).to_le_bytes());
+        assert_eq!((-2i64).as_seed(), (-2i64).to_le_bytes());
+        assert_eq!((-2i128).as_seed(), (-2i128).to_le_bytes());
+        assert_eq!(u64::SCHEMA, SeedSchema::Fixed(8));
+        assert_eq!(i128::SCHEMA, SeedSchema::Fixed(16));
+    }
+
+    #[test]
+    fn tuple_seeds() {
+        let seeds = (Const::<VaultSeed>::default(), [2; 32], 7u64);
+        assert_eq!(
+            <(Const<VaultSeed>, Pubkey, u64)>::SCHEMA,
+            Some(
+                &[
+                    SeedSchema::Literal(b"vault"),
+                    SeedSchema::Fixed(32),
+                    SeedSchema::Fixed(8),
+                ][..]
+            )
+        );
+        let expected: [&[u8]; 3] = [b"vault", &[2; 32], &7u64.to_le_bytes()];
+        seeds.with_seeds(|seeds| assert_eq!(seeds, expected));
+        seeds.with_seeds_and_bump(254, |seeds| {
+            assert_eq!(seeds[..3], expected);
+            assert_eq!(seeds[3], [254]);
+        });
+        assert!(seeds.seeds().eq(expected));
+
+        let fifteen = (
+            0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8,
+        );
+        fifteen.with_seeds_and_bump(255, |seeds| {
+            assert_eq!(seeds.len(), MAX_SEEDS);
+            assert!(seeds[..15]
+                .iter()
+                .zip(0..)
+                .all(|(seed, index)| seed == &[index]));
+            assert_eq!(seeds[15], [255]);
+        });
+    }
+
+    #[test]
+    fn rejects_long_variable_seeds() {
+        let long = [0; MAX_SEED_LEN + 1];
+        assert_eq!(
+            (&long[..],).try_find_program_address(&[0; 32]),
+            Err(ProgramError::MaxSeedLengthExceeded.into())
+        );
+    }
+}
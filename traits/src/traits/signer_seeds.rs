@@ -0,0 +1,112 @@
+use crate::error::AnchorResult;
+use crate::traits::account::Accounts;
+use core::array;
+use pinocchio::instruction::{Seed, Signer};
+use pinocchio::program_error::ProgramError;
+use pinocchio::pubkey::{Pubkey, MAX_SEEDS};
+
+pub trait SignerSeeds: Accounts {
+    /// Pushes the seeds of every account in `self` the current program can sign for.
+    fn signer_seeds<'a, const SEEDS: usize, const SIGNERS: usize>(
+        &'a self,
+        buffer: &mut SignerSeedsBuffer<'a, SEEDS, SIGNERS>,
+    ) -> AnchorResult;
+}
+
+/// Stack storage for up to `SIGNERS` PDA signers sharing `SEEDS` seeds, bumps included.
+pub struct SignerSeedsBuffer<'a, const SEEDS: usize, const SIGNERS: usize> {
+    seeds: [Seed<'a>; SEEDS],
+    seeds_len: usize,
+    signer_ends: [usize; SIGNERS],
+    signer_keys: [Pubkey; SIGNERS],
+    signers_len: usize,
+}
+impl<'a, const SEEDS: usize, const SIGNERS: usize> SignerSeedsBuffer<'a, SEEDS, SIGNERS> {
+    pub fn new() -> Self {
+        Self {
+            seeds: array::from_fn(|_| Seed::from(&[] as &[u8])),
+            seeds_len: 0,
+            signer_ends: [0; SIGNERS],
+            signer_keys: [[0; 32]; SIGNERS],
+            signers_len: 0,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.signers_len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.signers_len == 0
+    }
+
+    /// The addresses of the signers, in order.
+    #[inline]
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        &self.signer_keys[..self.signers_len]
+    }
+
+    /// Adds the signer `key`, `seeds` must already include the bump.
+    pub fn push_signer(
+        &mut self,
+        key: Pubkey,
+        seeds: impl IntoIterator<Item = &'a [u8]>,
+    ) -> AnchorResult {
+        if self.signers_len == SIGNERS {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+
+        let start = self.seeds_len;
+        for seed in seeds {
+            if self.seeds_len - start == MAX_SEEDS || self.seeds_len == SEEDS {
+                self.seeds_len = start;
+                return Err(ProgramError::MaxSeedLengthExceeded.into());
+            }
+            self.seeds[self.seeds_len] = Seed::from(seed);
+            self.seeds_len += 1;
+        }
+
+        self.signer_ends[self.signers_len] = self.seeds_len;
+        self.signer_keys[self.signers_len] = key;
+        self.signers_len += 1;
+        Ok(())
+    }
+
+    pub fn with_signers<O>(&self, f: impl FnOnce(&[Signer]) -> O) -> O {
+        let mut start = 0;
+        let signers: [Signer; SIGNERS] = array::from_fn(|index| {
+            if index < self.signers_len {
+                let end = self.signer_ends[index];
+                let signer = Signer::from(&self.seeds[start..end]);
+                start = end;
+                signer
+            } else {
+                Signer::from(&[] as &[Seed])
+            }
+        });
+        f(&signers[..self.signers_len])
+    }
+}
+impl<const SEEDS: usize, const SIGNERS: usize> Default for SignerSeedsBuffer<'_, SEEDS, SIGNERS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_signer_records_keys() {
+        let mut buffer = SignerSeedsBuffer::<'_, 4, 2>::new();
+        buffer.push_signer([1; 32], [&b"a"[..], &[255]]).unwrap();
+        buffer.push_signer([2; 32], [&b"b"[..]]).unwrap();
+        assert_eq!(buffer.signer_keys(), &[[1; 32], [2; 32]]);
+        assert!(buffer.push_signer([3; 32], []).is_err());
+        assert_eq!(buffer.len(), 2);
+        buffer.with_signers(|signers| assert_eq!(signers.len(), 2));
+    }
+}
@@ -1,5 +1,11 @@
+#[cfg(all(feature = "client", not(target_os = "solana")))]
+pub use crate::client::pda::{create_program_address, find_program_address};
+use core::fmt::{Debug, Formatter};
 use core::mem::{needs_drop, MaybeUninit};
-use core::ptr;
+use core::ops::{Deref, DerefMut};
+use core::{ptr, slice};
+#[cfg(not(all(feature = "client", not(target_os = "solana"))))]
+pub use pinocchio::pubkey::{create_program_address, find_program_address};
 
 pub fn try_map_array_init<I, O, E, const N: usize>(
     input: [I; N],
@@ -58,3 +64,78 @@ pub fn try_map_array_init<I, O, E, const N: usize>(
         Ok(unsafe { ptr::from_mut(&mut out_array).cast::<[O; N]>().read() })
     }
 }
+
+/// A `Vec` like buffer with a fixed capacity stored inline.
+pub struct StackVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+impl<T, const N: usize> StackVec<T, N> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Returns `item` back if the buffer is full.
+    #[inline]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                slot.write(item);
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+}
+impl<T, const N: usize> Deref for StackVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // Safety: The first `len` elements are initialized, and `MaybeUninit` is transparent to
+        // `T`.
+        unsafe { slice::from_raw_parts(self.items.as_ptr().cast::<T>(), self.len) }
+    }
+}
+impl<T, const N: usize> DerefMut for StackVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: The first `len` elements are initialized, and `MaybeUninit` is transparent to
+        // `T`.
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr().cast::<T>(), self.len) }
+    }
+}
+impl<T, const N: usize> Clone for StackVec<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut out = Self::new();
+        for item in self.iter() {
+            // Can't be full, `self` has the same capacity.
+            let _ = out.push(item.clone());
+        }
+        out
+    }
+}
+impl<T, const N: usize> Debug for StackVec<T, N>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T, const N: usize> Drop for StackVec<T, N> {
+    fn drop(&mut self) {
+        if const { needs_drop::<T>() } {
+            // Safety: We are only dropping initialized elements, and they are never read again.
+            unsafe { ptr::drop_in_place::<[T]>(&mut **self) }
+        }
+    }
+}